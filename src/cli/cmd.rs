@@ -1,3 +1,8 @@
+use crate::Result;
+use crate::support::clipboard::ClipboardKind;
+use crate::support::images::{self, ImageFormat, ImageTransforms};
+use crate::support::jsons_fmt::{SortKeys, TrailingCommas};
+use crate::support::zed::{self, ThemeMode, ZedTaskEnv};
 use clap::{Args, Parser, Subcommand, ValueEnum};
 
 #[derive(Parser, Debug)]
@@ -5,6 +10,10 @@ use clap::{Args, Parser, Subcommand, ValueEnum};
 pub struct CliCmd {
 	#[command(subcommand)]
 	pub command: CliSubCmd,
+
+	/// Print what would change (diffs of the file writes, skipped side effects) without doing it
+	#[arg(long, global = true)]
+	pub dry_run: bool,
 }
 
 #[derive(Subcommand, Debug)]
@@ -12,8 +21,11 @@ pub enum CliSubCmd {
 	/// Run AIP in a tmux session
 	TmuxRunAip(TmuxRunAipArgs),
 
-	/// Toggle AI in Zed settings (~/.config/zed/settings.json)
-	ZedToggleAi,
+	/// Toggle AI in Zed settings (~/.config/zed/settings.json, or <worktree>/.zed/settings.json with --project)
+	ZedToggleAi(ZedToggleAiArgs),
+
+	/// Zed AI settings information
+	ZedAi(ZedAiArgs),
 
 	/// Toggle through Zed profiles
 	ToggleProfile(ToggleProfileArgs),
@@ -23,29 +35,635 @@ pub enum CliSubCmd {
 
 	/// Convert a Markdown file to HTML
 	MdToHtml(MdToHtmlArgs),
+
+	/// Read or write the clipboard through the configured backend
+	Clipboard(ClipboardArgs),
+
+	/// Maintain the images of a docs directory referenced by Markdown files
+	Images(ImagesArgs),
+
+	/// Manage the jc-zed-tasks task definitions in Zed tasks (~/.config/zed/tasks.json)
+	ZedTasks(ZedTasksArgs),
+
+	/// Manage the jc-zed-tasks key bindings in Zed keymap (~/.config/zed/keymap.json)
+	ZedKeymap(ZedKeymapArgs),
+
+	/// Manage Zed snippets (~/.config/zed/snippets/<lang>.json)
+	ZedSnippet(ZedSnippetArgs),
+
+	/// Cycle or set the Zed theme and light/dark mode (optionally syncing the Alacritty colors)
+	ZedTheme(ZedThemeArgs),
+
+	/// Check the Zed settings (~/.config/zed/settings.json)
+	ZedSettings(ZedSettingsArgs),
+
+	/// Import settings and keybindings from other editors into the Zed config
+	ZedImport(ZedImportArgs),
+
+	/// Terminal color schemes from Zed themes
+	Theme(ThemeArgs),
+
+	/// List and diff the config file writes (backups in the state dir)
+	History(HistoryArgs),
+
+	/// Restore a config file to its content before a write (default the last write)
+	Undo(UndoArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct ZedTasksArgs {
+	#[command(subcommand)]
+	pub command: ZedTasksSubCmd,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ZedTasksSubCmd {
+	/// Install (merge) the task definitions of every subcommand into tasks.json
+	Install(ZedTasksInstallArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct ZedTasksInstallArgs {
+	/// Remove the task definitions instead of installing them
+	#[arg(long)]
+	pub uninstall: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct ZedToggleAiArgs {
+	/// Toggle in the project settings (<worktree>/.zed/settings.json) instead of the global one
+	#[arg(long)]
+	pub project: bool,
+
+	/// Project worktree root (default to ZED_WORKTREE_ROOT)
+	#[arg(long)]
+	pub worktree: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct ZedAiArgs {
+	#[command(subcommand)]
+	pub command: ZedAiSubCmd,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ZedAiSubCmd {
+	/// Show the effective `disable_ai` value across global and project settings
+	Status(ZedAiStatusArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct ZedAiStatusArgs {
+	/// Project worktree root (default to ZED_WORKTREE_ROOT)
+	#[arg(long)]
+	pub worktree: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct ZedSettingsArgs {
+	#[command(subcommand)]
+	pub command: ZedSettingsSubCmd,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ZedSettingsSubCmd {
+	/// Report duplicate keys, ambiguous key names, and profile values that cannot be updated (exit 1 if any)
+	Lint(ZedSettingsLintArgs),
+
+	/// Format the settings (comments stay attached to their keys)
+	Fmt(ZedSettingsFmtArgs),
+
+	/// Show the effective settings (global merged with project), with the source of each value
+	Effective(ZedSettingsEffectiveArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct ZedSettingsLintArgs {
+	/// Settings file (default to ~/.config/zed/settings.json)
+	#[arg(long)]
+	pub file: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct ZedSettingsFmtArgs {
+	/// Settings file (default to ~/.config/zed/settings.json)
+	#[arg(long)]
+	pub file: Option<String>,
+
+	/// Sort the keys
+	#[arg(long, value_enum, default_value = "none")]
+	pub sort: SortKeysArg,
+
+	/// Indentation, 'tab' or a number of spaces (default to the one of the file)
+	#[arg(long)]
+	pub indent: Option<String>,
+
+	/// Keep or remove the trailing commas
+	#[arg(long, value_enum, default_value = "keep")]
+	pub trailing_commas: TrailingCommasArg,
+
+	/// Only check that the file is formatted (exit 1 with the diff if not)
+	#[arg(long)]
+	pub check: bool,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum SortKeysArg {
+	None,
+	/// Sort the keys of the root object only
+	Top,
+	/// Sort the keys of all the objects
+	Recursive,
+}
+
+impl From<SortKeysArg> for SortKeys {
+	fn from(sort: SortKeysArg) -> Self {
+		match sort {
+			SortKeysArg::None => SortKeys::None,
+			SortKeysArg::Top => SortKeys::Top,
+			SortKeysArg::Recursive => SortKeys::Recursive,
+		}
+	}
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum TrailingCommasArg {
+	/// Keep a trailing comma where the object/array had one
+	Keep,
+	Remove,
+}
+
+impl From<TrailingCommasArg> for TrailingCommas {
+	fn from(trailing_commas: TrailingCommasArg) -> Self {
+		match trailing_commas {
+			TrailingCommasArg::Keep => TrailingCommas::Keep,
+			TrailingCommasArg::Remove => TrailingCommas::Remove,
+		}
+	}
+}
+
+#[derive(Args, Debug)]
+pub struct ZedSettingsEffectiveArgs {
+	/// Only the values at or below this key path (e.g., 'languages.Rust')
+	#[arg(long)]
+	pub path: Option<String>,
+
+	/// Apply the 'languages.<LANGUAGE>' overrides (e.g., 'Rust')
+	#[arg(long)]
+	pub language: Option<String>,
+
+	/// Project worktree root (default to ZED_WORKTREE_ROOT)
+	#[arg(long)]
+	pub worktree: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct ZedImportArgs {
+	#[command(subcommand)]
+	pub command: ZedImportSubCmd,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ZedImportSubCmd {
+	/// Import VS Code settings.json and keybindings.json (known mappings only, the others are reported)
+	Vscode(ZedImportVscodeArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct ZedImportVscodeArgs {
+	/// VS Code settings.json to merge into ~/.config/zed/settings.json
+	#[arg(long)]
+	pub settings: Option<String>,
+
+	/// VS Code keybindings.json to merge into ~/.config/zed/keymap.json
+	#[arg(long)]
+	pub keybindings: Option<String>,
 }
 
 #[derive(Args, Debug)]
 pub struct SaveClipboardImageArgs {
-	/// Directory where to save the image
+	/// Directory where to save the image (default to the Markdown file directory, else ZED_DIRNAME).
+	/// With {md_stem} (e.g., "{md_stem}.assets"), relative to the Markdown file directory
 	#[arg(long)]
-	pub dir: String,
+	pub dir: Option<String>,
+
+	/// Create the directory if it does not exist
+	#[arg(long)]
+	pub create_dir: bool,
+
+	/// Markdown file the image is for (default to ZED_FILE), for the relative path of the Markdown reference
+	#[arg(long)]
+	pub for_file: Option<String>,
+
+	/// Alt text of the Markdown reference (default derived from the file name)
+	#[arg(long)]
+	pub alt: Option<String>,
 
 	/// Copy Markdown reference to clipboard
 	#[arg(long)]
 	pub copy_md_ref: bool,
+
+	/// File name template, without extension: {n}, {n:03}, {date}, {time}, {md_stem}, {slug}
+	/// (default from the config, else "image-{n:02}")
+	#[arg(long)]
+	pub name: Option<String>,
+
+	/// Text for the {slug} placeholder (default to the Markdown file stem, ZED_FILE)
+	#[arg(long)]
+	pub slug: Option<String>,
+
+	/// Output format (png is lossless and optimized)
+	#[arg(long, value_enum, default_value_t = ImageFormatArg::Png)]
+	pub format: ImageFormatArg,
+
+	/// Quality for lossy formats, 1 to 100 (default 85)
+	#[arg(long)]
+	pub quality: Option<u8>,
+
+	/// Maximum file size in bytes (lossy formats step the quality down to fit)
+	#[arg(long)]
+	pub max_bytes: Option<usize>,
+
+	/// Trim the uniform borders, with an optional color tolerance per channel (default 10)
+	#[arg(long, value_name = "TOLERANCE", num_args = 0..=1, default_missing_value = "10")]
+	pub trim: Option<u8>,
+
+	/// Scale factor (e.g., 0.5 for HiDPI captures)
+	#[arg(long)]
+	pub scale: Option<f32>,
+
+	/// Maximum width in pixels (downscale to fit)
+	#[arg(long)]
+	pub max_width: Option<u32>,
+
+	/// Maximum height in pixels (downscale to fit)
+	#[arg(long)]
+	pub max_height: Option<u32>,
+
+	/// Padding in pixels
+	#[arg(long, default_value_t = 0)]
+	pub pad: u32,
+
+	/// Padding color, #rrggbb or #rrggbbaa (default transparent)
+	#[arg(long, default_value = "#00000000")]
+	pub pad_color: String,
+
+	/// Border width in pixels
+	#[arg(long, default_value_t = 0)]
+	pub border: u32,
+
+	/// Border color, #rrggbb or #rrggbbaa
+	#[arg(long, default_value = images::DEFAULT_BORDER_COLOR)]
+	pub border_color: String,
+
+	/// Drop shadow size in pixels
+	#[arg(long, default_value_t = 0)]
+	pub shadow: u32,
+
+	/// Always save a new file (do not reuse an existing image with the same pixels)
+	#[arg(long)]
+	pub no_dedup: bool,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum ImageFormatArg {
+	/// Lossless, optimized PNG
+	Png,
+	/// Lossy, with --quality and --max-bytes
+	Jpeg,
+	/// Lossless WebP
+	Webp,
+}
+
+impl From<ImageFormatArg> for ImageFormat {
+	fn from(format: ImageFormatArg) -> Self {
+		match format {
+			ImageFormatArg::Png => ImageFormat::Png,
+			ImageFormatArg::Jpeg => ImageFormat::Jpeg,
+			ImageFormatArg::Webp => ImageFormat::Webp,
+		}
+	}
 }
 
 #[derive(Args, Debug)]
-pub struct MdToHtmlArgs {
-	/// Path to the Markdown file
+pub struct ZedKeymapArgs {
+	#[command(subcommand)]
+	pub command: ZedKeymapSubCmd,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ZedKeymapSubCmd {
+	/// Install the `task::Spawn` bindings from the config (~/.config/jc-zed-tasks/config.json) into keymap.json
+	Install(ZedKeymapInstallArgs),
+
+	/// Report the conflicting bindings (duplicate, shadowed, prefix) and export a cheat sheet
+	Report(ZedKeymapReportArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct ZedKeymapInstallArgs {}
+
+#[derive(Args, Debug)]
+pub struct ZedKeymapReportArgs {
+	/// Keymap file (default ~/.config/zed/keymap.json)
+	#[arg(long)]
+	pub file: Option<String>,
+
+	/// Write the cheat sheet (grouped by context) to this file
 	#[arg(long)]
+	pub output: Option<String>,
+
+	/// Cheat sheet format
+	#[arg(long, value_enum, default_value = "md")]
+	pub format: CheatSheetFormat,
+}
+
+#[derive(Clone, Copy, Debug, ValueEnum)]
+pub enum CheatSheetFormat {
+	Md,
+	Html,
+}
+
+#[derive(Args, Debug)]
+pub struct ZedSnippetArgs {
+	#[command(subcommand)]
+	pub command: ZedSnippetSubCmd,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ZedSnippetSubCmd {
+	/// Add a snippet from the selection (ZED_SELECTED_TEXT) or stdin
+	Add(ZedSnippetAddArgs),
+
+	/// List the existing snippets
+	List(ZedSnippetListArgs),
+
+	/// Import a VS Code snippet file (.code-snippets or snippets/<lang>.json), without replacing existing snippets
+	ImportVscode(ZedSnippetImportVscodeArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct ZedSnippetAddArgs {
+	/// Language of the snippet file (e.g., rust, for snippets/rust.json)
+	#[arg(long)]
+	pub lang: String,
+
+	/// Snippet prefix (what triggers the completion)
+	#[arg(long)]
+	pub prefix: String,
+
+	/// Snippet name (default to the prefix)
+	#[arg(long)]
+	pub name: Option<String>,
+
+	/// Snippet description
+	#[arg(long)]
+	pub description: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct ZedSnippetListArgs {
+	/// Only list the snippets of this language (default all)
+	#[arg(long)]
+	pub lang: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct ZedSnippetImportVscodeArgs {
+	/// VS Code snippet file
 	pub file: String,
+
+	/// Zed language of the snippets without scope (default to the file name for snippets/<lang>.json, else all languages)
+	#[arg(long)]
+	pub lang: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct ZedThemeArgs {
+	#[command(subcommand)]
+	pub command: ZedThemeSubCmd,
+
+	/// Do not switch the Alacritty colors import
+	#[arg(long, global = true)]
+	pub no_alacritty: bool,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ZedThemeSubCmd {
+	/// Switch to the next theme of the config list
+	Next,
+
+	/// Switch to the previous theme of the config list
+	Prev,
+
+	/// Set the theme (for the current mode when theme is `{mode, light, dark}`)
+	Set(ZedThemeSetArgs),
+
+	/// Set the theme mode (converts a plain theme to `{mode, light, dark}`)
+	Mode(ZedThemeModeArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct ZedThemeSetArgs {
+	/// Theme name (e.g., "One Dark")
+	pub name: String,
+
+	/// Set as a plain theme name (converts `{mode, light, dark}` back to a string)
+	#[arg(long)]
+	pub plain: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct ZedThemeModeArgs {
+	#[arg(value_enum)]
+	pub mode: ThemeModeArg,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum ThemeModeArg {
+	Light,
+	Dark,
+	System,
+}
+
+impl From<ThemeModeArg> for ThemeMode {
+	fn from(mode: ThemeModeArg) -> Self {
+		match mode {
+			ThemeModeArg::Light => ThemeMode::Light,
+			ThemeModeArg::Dark => ThemeMode::Dark,
+			ThemeModeArg::System => ThemeMode::System,
+		}
+	}
+}
+
+#[derive(Args, Debug)]
+pub struct ThemeArgs {
+	#[command(subcommand)]
+	pub command: ThemeSubCmd,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ThemeSubCmd {
+	/// Export the Alacritty colors and tmux status/border snippet of a Zed theme
+	Export(ThemeExportArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct ThemeExportArgs {
+	/// Zed theme family file (path, or file name in ~/.config/zed/themes). Default to the themes dir file having the theme.
+	#[arg(long)]
+	pub from: Option<String>,
+
+	/// Theme name in the theme family (e.g., "One Dark")
+	#[arg(long)]
+	pub theme: String,
+
+	/// Output directory (default ~/.config/jc-zed-tasks/themes)
+	#[arg(long)]
+	pub out_dir: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct HistoryArgs {
+	#[command(subcommand)]
+	pub command: HistorySubCmd,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum HistorySubCmd {
+	/// List the config file writes
+	List,
+
+	/// Show the diff of a config file write
+	Diff(HistoryDiffArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct HistoryDiffArgs {
+	/// History entry id (see `history list`)
+	pub id: u64,
+}
+
+#[derive(Args, Debug)]
+pub struct UndoArgs {
+	/// History entry id (default the last write not undone)
+	pub id: Option<u64>,
+}
+
+#[derive(Args, Debug)]
+pub struct ClipboardArgs {
+	#[command(subcommand)]
+	pub command: ClipboardSubCmd,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ClipboardSubCmd {
+	/// Print (or save) the clipboard content
+	Get(ClipboardGetArgs),
+
+	/// Set the clipboard content from a file or stdin
+	Set(ClipboardSetArgs),
+
+	/// Print the clipboard backend in use (see JC_ZED_TASKS_CLIPBOARD)
+	Backend,
+}
+
+#[derive(Args, Debug)]
+pub struct ClipboardGetArgs {
+	/// Content kind (image is PNG)
+	#[arg(long, value_enum, default_value = "text")]
+	pub kind: ClipboardKindArg,
+
+	/// Save to this file instead of printing
+	#[arg(long)]
+	pub output: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct ClipboardSetArgs {
+	/// Content kind (image is PNG)
+	#[arg(long, value_enum, default_value = "text")]
+	pub kind: ClipboardKindArg,
+
+	/// Read the content from this file (default stdin)
+	#[arg(long)]
+	pub input: Option<String>,
+}
+
+#[derive(ValueEnum, Debug, Clone, Copy)]
+pub enum ClipboardKindArg {
+	Image,
+	Text,
+	Html,
+	UriList,
+}
+
+impl From<ClipboardKindArg> for ClipboardKind {
+	fn from(kind: ClipboardKindArg) -> Self {
+		match kind {
+			ClipboardKindArg::Image => ClipboardKind::Image,
+			ClipboardKindArg::Text => ClipboardKind::Text,
+			ClipboardKindArg::Html => ClipboardKind::Html,
+			ClipboardKindArg::UriList => ClipboardKind::UriList,
+		}
+	}
+}
+
+#[derive(Args, Debug)]
+pub struct ImagesArgs {
+	#[command(subcommand)]
+	pub command: ImagesSubCmd,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum ImagesSubCmd {
+	/// List the images not referenced by any Markdown file, and the broken image references
+	Orphans(ImagesOrphansArgs),
+
+	/// Renumber the image-NN.png files in order of first appearance, and rewrite the references in the worktree Markdown files
+	Renumber(ImagesRenumberArgs),
+}
+
+#[derive(Args, Debug)]
+pub struct ImagesOrphansArgs {
+	/// Images directory
+	#[arg(long)]
+	pub dir: String,
+
+	/// Root directory of the Markdown files, scanned recursively (default to ZED_WORKTREE_ROOT)
+	#[arg(long)]
+	pub md_root: Option<String>,
+
+	/// Delete the orphan images
+	#[arg(long, conflicts_with = "move_to")]
+	pub delete: bool,
+
+	/// Move the orphan images to this directory (e.g., a trash dir)
+	#[arg(long)]
+	pub move_to: Option<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct ImagesRenumberArgs {
+	/// Images directory
+	#[arg(long)]
+	pub dir: String,
+
+	/// Markdown file(s) referencing the images, in order (default to ZED_FILE)
+	#[arg(long)]
+	pub md: Vec<String>,
+}
+
+#[derive(Args, Debug)]
+pub struct MdToHtmlArgs {
+	/// Path to the Markdown file (default to ZED_FILE)
+	#[arg(long)]
+	pub file: Option<String>,
 }
 
 #[derive(Args, Debug)]
 pub struct TmuxRunAipArgs {
-	/// Filter by pane directory
+	/// Filter by pane directory (default to ZED_WORKTREE_ROOT)
 	#[arg(long)]
 	pub dir: Option<String>,
 
@@ -60,6 +678,89 @@ pub struct ToggleProfileArgs {
 	pub profile: Option<String>,
 }
 
+// region:    --- Zed Env Fallbacks
+
+impl ZedToggleAiArgs {
+	pub fn worktree(&self, zed_env: &ZedTaskEnv) -> Result<String> {
+		zed::arg_or_zed_var(
+			self.worktree.as_deref(),
+			"--worktree",
+			zed_env.worktree_root.as_deref(),
+			zed::ZED_WORKTREE_ROOT,
+		)
+	}
+}
+
+impl SaveClipboardImageArgs {
+	pub fn dir(&self, zed_env: &ZedTaskEnv) -> Result<String> {
+		zed::arg_or_zed_var(
+			self.dir.as_deref(),
+			"--dir",
+			zed_env.dirname.as_deref(),
+			zed::ZED_DIRNAME,
+		)
+	}
+
+	pub fn transforms(&self) -> Result<ImageTransforms> {
+		Ok(ImageTransforms {
+			trim: self.trim,
+			scale: self.scale,
+			max_width: self.max_width,
+			max_height: self.max_height,
+			pad: self.pad,
+			pad_color: images::parse_hex_color(&self.pad_color)?,
+			border: self.border,
+			border_color: images::parse_hex_color(&self.border_color)?,
+			shadow: self.shadow,
+		})
+	}
+
+	/// The Markdown file from `--for-file`, else ZED_FILE (optional).
+	pub fn for_file(&self, zed_env: &ZedTaskEnv) -> Option<String> {
+		self.for_file.clone().or_else(|| zed_env.file.clone())
+	}
+}
+
+impl MdToHtmlArgs {
+	pub fn file(&self, zed_env: &ZedTaskEnv) -> Result<String> {
+		zed::arg_or_zed_var(self.file.as_deref(), "--file", zed_env.file.as_deref(), zed::ZED_FILE)
+	}
+}
+
+impl ImagesOrphansArgs {
+	pub fn md_root(&self, zed_env: &ZedTaskEnv) -> Result<String> {
+		zed::arg_or_zed_var(
+			self.md_root.as_deref(),
+			"--md-root",
+			zed_env.worktree_root.as_deref(),
+			zed::ZED_WORKTREE_ROOT,
+		)
+	}
+}
+
+impl ImagesRenumberArgs {
+	pub fn md_files(&self, zed_env: &ZedTaskEnv) -> Result<Vec<String>> {
+		if !self.md.is_empty() {
+			return Ok(self.md.clone());
+		}
+		let file = zed::arg_or_zed_var(None, "--md", zed_env.file.as_deref(), zed::ZED_FILE)?;
+		Ok(vec![file])
+	}
+}
+
+impl TmuxRunAipArgs {
+	pub fn dir(&self, zed_env: &ZedTaskEnv) -> Result<String> {
+		zed::arg_or_zed_var(
+			self.dir.as_deref(),
+			"--dir",
+			zed_env.worktree_root.as_deref(),
+			zed::ZED_WORKTREE_ROOT,
+		)
+	}
+}
+
+// endregion: --- Zed Env Fallbacks
+
 #[derive(Args, Debug)]
 pub struct NewDevTermArgs {
 	/// Working directory for the new terminal
@@ -0,0 +1,120 @@
+use crate::Result;
+use crate::cli::cmd::{ClipboardArgs, ClipboardGetArgs, ClipboardSetArgs, ClipboardSubCmd};
+use crate::support::clipboard::{self, ClipboardBackend, ClipboardKind};
+use crate::support::writer;
+use simple_fs::SPath;
+use std::fs;
+use std::io::{IsTerminal as _, Read as _, Write as _};
+
+pub fn exec_command(args: ClipboardArgs) -> Result<()> {
+	match args.command {
+		ClipboardSubCmd::Get(args) => exec_get(args, clipboard::backend()?.as_mut()),
+		ClipboardSubCmd::Set(args) => exec_set(args, clipboard::backend()?.as_mut()),
+		ClipboardSubCmd::Backend => {
+			println!("{}", clipboard::backend()?.name());
+			Ok(())
+		}
+	}
+}
+
+// region:    --- Support
+
+fn exec_get(args: ClipboardGetArgs, backend: &mut dyn ClipboardBackend) -> Result<()> {
+	let kind = ClipboardKind::from(args.kind);
+	let content = backend.read(kind)?;
+
+	match args.output {
+		Some(output) => writer::write_file(&SPath::new(output), content)?,
+		None => {
+			if kind == ClipboardKind::Image && std::io::stdout().is_terminal() {
+				return Err("Not printing a PNG to the terminal, use --output <file>".into());
+			}
+			std::io::stdout().write_all(&content)?;
+		}
+	}
+
+	Ok(())
+}
+
+fn exec_set(args: ClipboardSetArgs, backend: &mut dyn ClipboardBackend) -> Result<()> {
+	let kind = ClipboardKind::from(args.kind);
+	let content = match args.input {
+		Some(input) => fs::read(input)?,
+		None => {
+			let mut content = Vec::new();
+			std::io::stdin().read_to_end(&mut content)?;
+			content
+		}
+	};
+
+	if writer::side_effect(&format!(
+		"set the clipboard {} ({} bytes) with {}",
+		kind.as_str(),
+		content.len(),
+		backend.name()
+	)) {
+		backend.write(kind, &content)?;
+	}
+
+	Ok(())
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+	use crate::cli::cmd::ClipboardKindArg;
+	use crate::support::clipboard::FileBackend;
+
+	#[test]
+	fn test_cli_exec_clipboard_set_get_file_backend() -> Result<()> {
+		// -- Setup & Fixtures
+		let dir = std::env::temp_dir().join(format!("jc-zed-tasks-test-exec-clipboard-{}", std::process::id()));
+		fs::create_dir_all(&dir)?;
+		let dir = SPath::from_std_path(&dir)?;
+		let mut backend = FileBackend::new(dir.join("clipboard"));
+		let input = dir.join("in.html");
+		let output = dir.join("out.html");
+		fs::write(input.std_path(), "<b>hi</b>")?;
+
+		// -- Exec
+		exec_set(
+			ClipboardSetArgs {
+				kind: ClipboardKindArg::Html,
+				input: Some(input.to_string()),
+			},
+			&mut backend,
+		)?;
+		exec_get(
+			ClipboardGetArgs {
+				kind: ClipboardKindArg::Html,
+				output: Some(output.to_string()),
+			},
+			&mut backend,
+		)?;
+		let text_res = exec_get(
+			ClipboardGetArgs {
+				kind: ClipboardKindArg::Text,
+				output: Some(dir.join("out.txt").to_string()),
+			},
+			&mut backend,
+		);
+
+		// -- Check
+		assert_eq!(fs::read_to_string(output.std_path())?, "<b>hi</b>");
+		assert_eq!(backend.read(ClipboardKind::Html)?, b"<b>hi</b>");
+		assert!(text_res.is_err(), "only the html kind was set");
+
+		// -- Cleanup
+		fs::remove_dir_all(dir.std_path())?;
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
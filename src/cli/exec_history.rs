@@ -0,0 +1,67 @@
+use crate::Result;
+use crate::cli::cmd::{HistoryArgs, HistoryDiffArgs, HistorySubCmd, UndoArgs};
+use crate::support::history::History;
+use crate::support::{diffs, writer};
+
+pub fn exec_command(args: HistoryArgs) -> Result<()> {
+	match args.command {
+		HistorySubCmd::List => exec_list(),
+		HistorySubCmd::Diff(args) => exec_diff(args),
+	}
+}
+
+pub fn exec_undo(args: UndoArgs) -> Result<()> {
+	let history = History::open()?;
+	let entry = history.undo(args.id)?;
+
+	let action = if writer::is_dry_run() {
+		"Would restore"
+	} else {
+		"Restored"
+	};
+	println!("{action} {} to before #{} ({})", entry.file, entry.id, entry.command);
+
+	Ok(())
+}
+
+// region:    --- Support
+
+fn exec_list() -> Result<()> {
+	let entries = History::open()?.list()?;
+	if entries.is_empty() {
+		println!("No config file writes in history");
+		return Ok(());
+	}
+
+	for entry in entries {
+		let before = entry.before_hash.as_deref().unwrap_or("(new file)");
+		let after = entry.after_hash.as_deref().unwrap_or("(removed)");
+		let undone = if entry.undone { " [undone]" } else { "" };
+		println!(
+			"#{:<4} {}  {:<16} {}  {before} -> {after}{undone}",
+			entry.id, entry.time, entry.command, entry.file
+		);
+	}
+
+	Ok(())
+}
+
+fn exec_diff(args: HistoryDiffArgs) -> Result<()> {
+	let history = History::open()?;
+	let entry = history.get(args.id)?;
+
+	let before = history.before_content(&entry)?.unwrap_or_default();
+	let after = history.after_content(&entry)?.unwrap_or_default();
+
+	let diff = diffs::unified_diff(
+		&before,
+		&after,
+		&format!("{} (before #{})", entry.file, entry.id),
+		&format!("{} (after #{})", entry.file, entry.id),
+	);
+	print!("{diff}");
+
+	Ok(())
+}
+
+// endregion: --- Support
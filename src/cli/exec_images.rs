@@ -0,0 +1,265 @@
+use crate::Result;
+use crate::cli::cmd::{ImagesArgs, ImagesOrphansArgs, ImagesRenumberArgs, ImagesSubCmd};
+use crate::support::images::{
+	image_number, md_image_refs, normalize_path, renumber_plan, resolve_image_ref, rewrite_image_refs,
+};
+use crate::support::writer;
+use crate::support::zed::ZedTaskEnv;
+use simple_fs::{SPath, list_files, read_to_string};
+use std::collections::{HashMap, HashSet};
+use std::fs;
+use std::path::PathBuf;
+
+/// The image file extensions of the images dir.
+const IMAGE_GLOBS: &[&str] = &["**/*.png", "**/*.jpg", "**/*.jpeg", "**/*.gif", "**/*.webp", "**/*.svg"];
+
+const MD_GLOBS: &[&str] = &["**/*.md", "**/*.markdown"];
+
+pub fn exec_command(args: ImagesArgs, zed_env: &ZedTaskEnv) -> Result<()> {
+	match args.command {
+		ImagesSubCmd::Orphans(args) => exec_orphans(args, zed_env),
+		ImagesSubCmd::Renumber(args) => exec_renumber(args, zed_env),
+	}
+}
+
+// region:    --- Support
+
+fn exec_orphans(args: ImagesOrphansArgs, zed_env: &ZedTaskEnv) -> Result<()> {
+	let dir = SPath::new(&args.dir);
+	if !dir.exists() {
+		return Err(format!("Directory does not exist: {dir}").into());
+	}
+	let md_root = SPath::new(args.md_root(zed_env)?);
+	let md_root_abs = absolute(&md_root)?;
+
+	// -- Referenced images and broken references
+	let mut referenced: HashSet<PathBuf> = HashSet::new();
+	let mut broken: Vec<String> = Vec::new();
+	let md_files = list_files(&md_root, Some(MD_GLOBS), None)?;
+	for md_file in md_files.iter() {
+		let content = read_to_string(md_file)?;
+		let md_dir = absolute(md_file)?
+			.parent()
+			.map(|dir| dir.to_path_buf())
+			.unwrap_or_default();
+		for image_ref in md_image_refs(&content) {
+			let Some(path) = resolve_image_ref(&image_ref.url, &md_dir, &md_root_abs) else {
+				continue;
+			};
+			if !path.exists() {
+				broken.push(format!("{md_file}:{}  {}", image_ref.line, image_ref.url));
+			}
+			referenced.insert(path);
+		}
+	}
+
+	// -- Orphans
+	let mut orphans: Vec<SPath> = Vec::new();
+	for image in list_files(&dir, Some(IMAGE_GLOBS), None)? {
+		if !referenced.contains(&absolute(&image)?) {
+			orphans.push(image);
+		}
+	}
+	orphans.sort_by(|a, b| a.as_str().cmp(b.as_str()));
+
+	println!("Scanned {} Markdown file(s) in {md_root}", md_files.len());
+	if !broken.is_empty() {
+		println!("\nBroken references ({}):", broken.len());
+		for line in broken.iter() {
+			println!("  {line}");
+		}
+	}
+	if orphans.is_empty() {
+		println!("\nNo orphan images in {dir}");
+		return Ok(());
+	}
+	println!("\nOrphan images ({}):", orphans.len());
+	for orphan in orphans.iter() {
+		println!("  {orphan}");
+	}
+
+	// -- Clean
+	if args.delete {
+		for orphan in orphans.iter() {
+			writer::remove_file(orphan)?;
+		}
+		if !writer::is_dry_run() {
+			println!("\nDeleted {} orphan image(s)", orphans.len());
+		}
+	} else if let Some(move_to) = args.move_to.as_deref() {
+		let move_to = SPath::new(move_to);
+		if !move_to.exists() && writer::side_effect(&format!("create directory {move_to}")) {
+			fs::create_dir_all(move_to.std_path())?;
+		}
+		let mut moved = 0;
+		for orphan in orphans.iter() {
+			let dest = move_to.join(orphan.name());
+			if dest.exists() {
+				println!("Skipped {orphan} ({dest} already exists)");
+				continue;
+			}
+			if writer::side_effect(&format!("move {orphan} to {dest}")) {
+				// Note: The rename fails across file systems, so copy and remove then
+				fs::rename(orphan.std_path(), dest.std_path()).or_else(|_| {
+					fs::copy(orphan.std_path(), dest.std_path()).and_then(|_| fs::remove_file(orphan.std_path()))
+				})?;
+				moved += 1;
+			}
+		}
+		if !writer::is_dry_run() {
+			println!("\nMoved {moved} orphan image(s) to {move_to}");
+		}
+	}
+
+	Ok(())
+}
+
+fn exec_renumber(args: ImagesRenumberArgs, zed_env: &ZedTaskEnv) -> Result<()> {
+	let dir = SPath::new(&args.dir);
+	if !dir.exists() {
+		return Err(format!("Directory does not exist: {dir}").into());
+	}
+	let dir_abs = absolute(&dir)?;
+	// Note: The `/...` references are relative to the worktree root (else the current dir)
+	let root = SPath::new(zed_env.worktree_root.as_deref().unwrap_or("."));
+	let root_dir = absolute(&root)?;
+
+	let mut md_files: Vec<(SPath, PathBuf, String)> = Vec::new();
+	for md_file in args.md_files(zed_env)? {
+		md_files.push(read_md_file(SPath::new(md_file))?);
+	}
+
+	// -- The other Markdown files of the worktree (their references to the renamed images are rewritten too)
+	let mut given: HashSet<PathBuf> = HashSet::new();
+	for (md_file, _, _) in md_files.iter() {
+		given.insert(absolute(md_file)?);
+	}
+	let mut other_md_files: Vec<(SPath, PathBuf, String)> = Vec::new();
+	for md_file in list_files(&root, Some(MD_GLOBS), None)? {
+		if !given.contains(&absolute(&md_file)?) {
+			other_md_files.push(read_md_file(md_file)?);
+		}
+	}
+
+	// -- Plan, from the first appearance of the dir images
+	let file_names: Vec<String> = list_files(&dir, Some(&["image-*.png"]), None)?
+		.iter()
+		.map(|file| file.name().to_string())
+		.collect();
+	let mut first_refs: Vec<String> = Vec::new();
+	for (_, md_dir, content) in md_files.iter() {
+		for image_ref in md_image_refs(content) {
+			let Some(path) = resolve_image_ref(&image_ref.url, md_dir, &root_dir) else {
+				continue;
+			};
+			if path.parent() == Some(dir_abs.as_path())
+				&& let Some(name) = path.file_name()
+			{
+				first_refs.push(name.to_string_lossy().to_string());
+			}
+		}
+	}
+	let plan = renumber_plan(&file_names, &first_refs);
+	let kept: Vec<&String> = file_names
+		.iter()
+		.filter(|name| image_number(name).is_some() && !first_refs.contains(name))
+		.collect();
+	if !kept.is_empty() {
+		let kept: Vec<&str> = kept.iter().map(|name| name.as_str()).collect();
+		println!("Not referenced by the Markdown file(s), kept: {}", kept.join(", "));
+	}
+	if plan.is_empty() {
+		println!("Images already numbered in order in {dir}");
+		return Ok(());
+	}
+
+	println!("Renumber ({} image(s) in {dir}):", plan.len());
+	for (old, new) in plan.iter() {
+		println!("  {old} -> {new}");
+	}
+
+	// -- The rewritten references (computed before any change)
+	let renames: HashMap<PathBuf, String> = plan.iter().map(|(old, new)| (dir_abs.join(old), new.clone())).collect();
+	let mut rewrites: Vec<(&SPath, &str, String, usize)> = Vec::new();
+	for (md_file, md_dir, content) in md_files.iter().chain(other_md_files.iter()) {
+		let (new_content, count) = rewrite_image_refs(content, md_dir, &root_dir, &renames);
+		if count > 0 {
+			rewrites.push((md_file, content, new_content, count));
+		}
+	}
+
+	if writer::is_dry_run() {
+		println!("Would rename {} image(s)", plan.len());
+		for (md_file, _, new_content, _) in rewrites.iter() {
+			writer::write_file(md_file, new_content)?;
+		}
+		return Ok(());
+	}
+
+	// -- Rename, then rewrite the references (everything rolled back on error)
+	rename_files(&dir, &plan)?;
+	for (idx, (md_file, _, new_content, count)) in rewrites.iter().enumerate() {
+		if let Err(err) = writer::write_file(md_file, new_content) {
+			for (written_file, content, _, _) in rewrites.iter().take(idx) {
+				if let Err(restore_err) = writer::write_file(written_file, content) {
+					eprintln!("Warning: Cannot restore {written_file}. Cause: {restore_err}");
+				}
+			}
+			let reverse_plan: Vec<(String, String)> =
+				plan.iter().map(|(old, new)| (new.clone(), old.clone())).collect();
+			rename_files(&dir, &reverse_plan)?;
+			return Err(format!("Cannot rewrite {md_file} (images and references restored). Cause: {err}").into());
+		}
+		println!("Rewrote {count} reference(s) in {md_file}");
+	}
+
+	Ok(())
+}
+
+/// The Markdown file, its absolute dir, and its content.
+fn read_md_file(md_file: SPath) -> Result<(SPath, PathBuf, String)> {
+	let content = read_to_string(&md_file)?;
+	let md_dir = absolute(&md_file)?
+		.parent()
+		.map(|dir| dir.to_path_buf())
+		.unwrap_or_default();
+	Ok((md_file, md_dir, content))
+}
+
+/// Rename the files of the dir in two phases (to temp names, then the new names) to avoid collisions.
+/// On error, the renames already done are reverted.
+fn rename_files(dir: &SPath, plan: &[(String, String)]) -> Result<()> {
+	let mut done: Vec<(SPath, SPath)> = Vec::new();
+	let Err(err) = rename_files_two_phases(dir, plan, &mut done) else {
+		return Ok(());
+	};
+
+	for (from, to) in done.iter().rev() {
+		if let Err(rollback_err) = fs::rename(to.std_path(), from.std_path()) {
+			eprintln!("Warning: Cannot rename {to} back to {from}. Cause: {rollback_err}");
+		}
+	}
+	Err(format!("Cannot rename the images in {dir} (renames reverted). Cause: {err}").into())
+}
+
+fn rename_files_two_phases(dir: &SPath, plan: &[(String, String)], done: &mut Vec<(SPath, SPath)>) -> Result<()> {
+	let pid = std::process::id();
+	let temp_path = |name: &str| dir.join(format!(".{name}.{pid}.renumber"));
+	for (old, _) in plan.iter() {
+		let (from, to) = (dir.join(old), temp_path(old));
+		fs::rename(from.std_path(), to.std_path())?;
+		done.push((from, to));
+	}
+	for (old, new) in plan.iter() {
+		let (from, to) = (temp_path(old), dir.join(new));
+		fs::rename(from.std_path(), to.std_path())?;
+		done.push((from, to));
+	}
+	Ok(())
+}
+
+fn absolute(path: &SPath) -> Result<PathBuf> {
+	Ok(normalize_path(&std::path::absolute(path.std_path())?))
+}
+
+// endregion: --- Support
@@ -0,0 +1,272 @@
+use crate::Result;
+use crate::cli::cmd::SaveClipboardImageArgs;
+use crate::support::config::{self, SaveClipboardImageConfig};
+use crate::support::images::{
+	DEFAULT_IMAGE_NAME_TEMPLATE, ImageFormat, NameContext, NameTemplate, alt_from_file_name, encode_image,
+	find_same_image, format_bytes, md_image_ref, record_image, relative_path,
+};
+use crate::support::zed::{self, ZedTaskEnv};
+use crate::support::clipboard::{self, ClipboardBackend, ClipboardKind};
+use crate::support::{files, writer};
+use image::DynamicImage;
+use simple_fs::{SPath, list_files};
+
+pub fn exec_command(args: SaveClipboardImageArgs, zed_env: &ZedTaskEnv) -> Result<()> {
+	zed::touch_tasks_json(zed_env)?;
+
+	save_clipboard_image(args, zed_env, clipboard::backend()?.as_mut())
+}
+
+// region:    --- Support
+
+fn save_clipboard_image(
+	args: SaveClipboardImageArgs,
+	zed_env: &ZedTaskEnv,
+	backend: &mut dyn ClipboardBackend,
+) -> Result<()> {
+	let md_file = args.for_file(zed_env).map(SPath::new);
+	let md_dir = md_file.as_ref().map(|file| {
+		file.parent()
+			.filter(|dir| !dir.as_str().is_empty())
+			.unwrap_or_else(|| SPath::new("."))
+	});
+	let md_stem = md_file.as_ref().map(|file| file.stem().to_string());
+
+	// -- Target dir (relative to the current dir, or to the Markdown file dir when using {md_stem})
+	let dir = match (args.dir.as_deref(), md_dir.as_ref()) {
+		(Some(dir), _) if !dir.contains("{md_stem}") => SPath::new(dir),
+		(Some(dir), Some(md_dir)) => {
+			let dir = dir.replace("{md_stem}", md_stem.as_deref().unwrap_or_default());
+			if SPath::new(&dir).std_path().is_relative() {
+				md_dir.join(&dir)
+			} else {
+				SPath::new(dir)
+			}
+		}
+		(Some(_), None) => {
+			return Err("--dir uses {md_stem} but there is no Markdown file (--for-file or ZED_FILE)".into());
+		}
+		(None, Some(md_dir)) => md_dir.clone(),
+		(None, None) => SPath::new(args.dir(zed_env)?),
+	};
+	if !dir.exists() {
+		if !args.create_dir {
+			return Err(format!("Directory does not exist: {dir} (use --create-dir to create it)").into());
+		}
+		if writer::side_effect(&format!("create directory {dir}")) {
+			std::fs::create_dir_all(dir.std_path())?;
+			println!("Created directory: {dir}");
+		}
+	}
+
+	// -- Clipboard image (transformed)
+	let png_bytes = backend.read(ClipboardKind::Image)?;
+	let mut img =
+		image::load_from_memory(&png_bytes).map_err(|e| format!("Cannot decode clipboard image. Cause: {e}"))?;
+	let transforms = args.transforms()?;
+	if !transforms.is_empty() {
+		let (width, height) = (img.width(), img.height());
+		img = DynamicImage::ImageRgba8(transforms.apply(img.to_rgba8())?);
+		println!("Transform: {width}x{height} -> {}x{}", img.width(), img.height());
+	}
+	let rgba = img.to_rgba8();
+
+	// -- Reuse the same image, else save a new one
+	let same_image = if args.no_dedup || !dir.exists() {
+		None
+	} else {
+		find_same_image(&dir, &rgba)?
+	};
+	let (file_name, dest_path) = match same_image {
+		Some(file_name) => {
+			println!("Reused existing {file_name}");
+			let dest_path = dir.join(&file_name);
+			(file_name, dest_path)
+		}
+		None => {
+			let file_name = next_file_name(&args, &dir, md_stem)?;
+			let dest_path = dir.join(&file_name);
+			save_image(&args, &img, &png_bytes, !transforms.is_empty(), &dest_path)?;
+			record_image(&dir, &file_name, &rgba)?;
+			if !writer::is_dry_run() {
+				println!("Image saved to: {dest_path}");
+			}
+			(file_name, dest_path)
+		}
+	};
+
+	if args.copy_md_ref && writer::side_effect("copy the Markdown reference to the clipboard") {
+		let path = match md_dir.as_ref() {
+			Some(md_dir) => relative_path(
+				&std::path::absolute(md_dir.std_path())?,
+				&std::path::absolute(dest_path.std_path())?,
+			),
+			None => file_name.clone(),
+		};
+		let alt = args.alt.clone().unwrap_or_else(|| alt_from_file_name(&file_name));
+		let md_ref = md_image_ref(&alt, &path);
+		backend.write(ClipboardKind::Text, md_ref.as_bytes())?;
+		println!("Markdown reference copied to clipboard: {md_ref}");
+	}
+
+	Ok(())
+}
+
+/// The next file name from the `--name` template, else the config one.
+fn next_file_name(args: &SaveClipboardImageArgs, dir: &SPath, md_stem: Option<String>) -> Result<String> {
+	let template = match args.name.as_deref() {
+		Some(name) => name.to_string(),
+		None => {
+			// Note: A bad config.json must not fail the save (the clipboard image would not be saved)
+			let image_config = match config::load_config() {
+				Ok(app_config) => app_config.save_clipboard_image,
+				Err(err) => {
+					eprintln!("Warning: Cannot load the config, using the default image name template. Cause: {err}");
+					Default::default()
+				}
+			};
+			default_name_template(&image_config, dir)?
+		}
+	};
+	let template = NameTemplate::parse(&template)?;
+	let ctx = NameContext {
+		now: chrono::Local::now().naive_local(),
+		md_stem,
+		slug: args.slug.clone(),
+	};
+	let existing: Vec<String> = if dir.exists() {
+		list_files(dir, Some(&["*.*"]), None)?
+			.iter()
+			.map(|f| f.name().to_string())
+			.collect()
+	} else {
+		Vec::new()
+	};
+	template.next_name(&ctx, &existing, ImageFormat::from(args.format).ext())
+}
+
+/// Encodes the image in the `--format` (printing the before and after sizes) and writes it.
+fn save_image(
+	args: &SaveClipboardImageArgs,
+	img: &DynamicImage,
+	png_bytes: &[u8],
+	transformed: bool,
+	dest_path: &SPath,
+) -> Result<()> {
+	let format: ImageFormat = args.format.into();
+	let mut encoded = encode_image(img, format, args.quality, args.max_bytes)?;
+	// Note: The optimized PNG can be larger than the clipboard one for tiny images (palette chunk overhead)
+	if format == ImageFormat::Png && !transformed && png_bytes.len() < encoded.bytes.len() {
+		encoded.fits = args.max_bytes.is_none_or(|max| png_bytes.len() <= max);
+		encoded.bytes = png_bytes.to_vec();
+	}
+	let quality = encoded.quality.map(|q| format!(" q{q}")).unwrap_or_default();
+	println!(
+		"Size: {} (clipboard) -> {} ({}{quality})",
+		format_bytes(png_bytes.len()),
+		format_bytes(encoded.bytes.len()),
+		format.ext()
+	);
+	if !encoded.fits
+		&& let Some(max_bytes) = args.max_bytes
+	{
+		println!("Warning: Could not fit in --max-bytes {}", format_bytes(max_bytes));
+	}
+	writer::write_file(dest_path, encoded.bytes)
+}
+
+/// The name template of the deepest config `dirs` entry containing the dir, else the config `name`, else the default.
+fn default_name_template(config: &SaveClipboardImageConfig, dir: &SPath) -> Result<String> {
+	// Note: The dir might not exist yet in dry-run (with --create-dir)
+	let dir = std::fs::canonicalize(dir.std_path()).or_else(|_| std::path::absolute(dir.std_path()))?;
+
+	let mut best: Option<(usize, &str)> = None;
+	for dir_config in config.dirs.iter() {
+		let Some(name) = dir_config.name.as_deref() else {
+			continue;
+		};
+		let config_dir = files::expand_home(&dir_config.dir)?;
+		let Ok(config_dir) = std::fs::canonicalize(config_dir.std_path()) else {
+			continue;
+		};
+		let depth = config_dir.components().count();
+		if dir.starts_with(&config_dir) && best.is_none_or(|(best_depth, _)| depth > best_depth) {
+			best = Some((depth, name));
+		}
+	}
+
+	let template = best
+		.map(|(_, name)| name)
+		.or(config.name.as_deref())
+		.unwrap_or(DEFAULT_IMAGE_NAME_TEMPLATE);
+
+	Ok(template.to_string())
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+	use crate::cli::cmd::{CliCmd, CliSubCmd};
+	use crate::support::clipboard::FileBackend;
+	use clap::Parser as _;
+	use image::{Rgba, RgbaImage};
+	use std::fs;
+	use std::io::Cursor;
+
+	#[test]
+	fn test_cli_exec_save_image_file_backend_dedup() -> Result<()> {
+		// -- Setup & Fixtures
+		let dir = std::env::temp_dir().join(format!("jc-zed-tasks-test-exec-save-image-{}", std::process::id()));
+		fs::create_dir_all(&dir)?;
+		let dir = SPath::from_std_path(&dir)?;
+		let mut backend = FileBackend::new(dir.join("clipboard"));
+		let docs_dir = dir.join("docs");
+		fs::create_dir_all(docs_dir.std_path())?;
+		let md_file = docs_dir.join("notes.md");
+		let mut png_bytes = Vec::new();
+		RgbaImage::from_pixel(4, 3, Rgba([0x20, 0x40, 0x60, 0xff]))
+			.write_to(&mut Cursor::new(&mut png_bytes), image::ImageFormat::Png)?;
+		let args = || -> Result<SaveClipboardImageArgs> {
+			let cmd = CliCmd::try_parse_from([
+				"jc-zed-tasks",
+				"save-clipboard-image",
+				"--for-file",
+				md_file.as_str(),
+				"--name",
+				"image-{n:02}",
+				"--copy-md-ref",
+			])?;
+			match cmd.command {
+				CliSubCmd::SaveClipboardImage(args) => Ok(args),
+				_ => Err("not a save-clipboard-image command".into()),
+			}
+		};
+
+		// -- Exec
+		backend.write(ClipboardKind::Image, &png_bytes)?;
+		save_clipboard_image(args()?, &ZedTaskEnv::default(), &mut backend)?;
+		let md_ref = String::from_utf8(backend.read(ClipboardKind::Text)?)?;
+		backend.write(ClipboardKind::Image, &png_bytes)?;
+		save_clipboard_image(args()?, &ZedTaskEnv::default(), &mut backend)?;
+		let md_ref_again = String::from_utf8(backend.read(ClipboardKind::Text)?)?;
+
+		// -- Check
+		assert!(docs_dir.join("image-01.png").exists());
+		assert!(!docs_dir.join("image-02.png").exists(), "the same image should be reused");
+		assert_eq!(md_ref, "![image 01](image-01.png)");
+		assert_eq!(md_ref_again, md_ref);
+
+		// -- Cleanup
+		fs::remove_dir_all(dir.std_path())?;
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
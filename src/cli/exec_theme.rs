@@ -0,0 +1,55 @@
+use crate::Result;
+use crate::cli::cmd::{ThemeArgs, ThemeExportArgs, ThemeSubCmd};
+use crate::support::theme_export::TerminalColors;
+use crate::support::{config, text, writer, zed};
+use simple_fs::SPath;
+
+pub fn exec_command(args: ThemeArgs) -> Result<()> {
+	match args.command {
+		ThemeSubCmd::Export(args) => exec_export(args),
+	}
+}
+
+// region:    --- Support
+
+fn exec_export(args: ThemeExportArgs) -> Result<()> {
+	let theme_file = zed::find_theme_file(args.from.as_deref(), &args.theme)?;
+	let family = zed::load_theme_family(&theme_file)?;
+
+	let theme = family.themes.iter().find(|t| t.name == args.theme).ok_or_else(|| {
+		let names: Vec<&str> = family.themes.iter().map(|t| t.name.as_str()).collect();
+		format!(
+			"Theme '{}' not found in {theme_file} (available: {})",
+			args.theme,
+			names.join(", ")
+		)
+	})?;
+
+	let colors = TerminalColors::from_zed_theme(theme)?;
+
+	// -- Write the files
+	let out_dir = match args.out_dir {
+		Some(out_dir) => SPath::new(out_dir),
+		None => config::config_dir()?.join("themes"),
+	};
+	let slug = text::slugify(&theme.name, '_');
+	let alacritty_path = out_dir.join(format!("{slug}.alacritty.toml"));
+	let tmux_path = out_dir.join(format!("{slug}.tmux.conf"));
+
+	writer::write_file(&alacritty_path, colors.to_alacritty_toml())?;
+	writer::write_file(&tmux_path, colors.to_tmux_conf())?;
+
+	let appearance = theme.appearance.as_deref().unwrap_or("unknown appearance");
+	println!(
+		"Exported Zed theme '{}' ({appearance}) of family '{}' from {theme_file}",
+		theme.name, family.name
+	);
+	println!("Alacritty colors: {alacritty_path}");
+	println!("  alacritty.toml: [general] import = [\"{alacritty_path}\"]");
+	println!("tmux snippet:     {tmux_path}");
+	println!("  tmux.conf:      source-file {tmux_path}");
+
+	Ok(())
+}
+
+// endregion: --- Support
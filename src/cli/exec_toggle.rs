@@ -5,12 +5,12 @@ use crate::support::mac::{
 	APP_NAME_ALACRITTY, WindowBounds, get_front_window_bounds, move_window_front_by_window_name,
 	set_front_window_bounds,
 };
-use crate::support::{alacritty, jsons, tomls, zed};
+use crate::support::zed::{self, ZedTaskEnv};
+use crate::support::{alacritty, config, files, history, jsons, tomls, writer};
 use serde::{Deserialize, Serialize};
 use serde_json::json;
 use simple_fs::{SPath, home_dir, read_to_string};
 use std::collections::HashMap;
-use std::fs;
 
 // region:    --- Types
 
@@ -32,7 +32,7 @@ pub(crate) struct Profile {
 
 #[derive(Deserialize, Serialize)]
 pub(crate) struct ConfigEntry {
-	config_path: Vec<String>,
+	pub(crate) config_path: Vec<String>,
 	value: serde_json::Value,
 }
 
@@ -58,13 +58,13 @@ impl Default for TerminalDims {
 
 // endregion: --- Types
 
-pub fn exec_command(args: ToggleProfileArgs) -> Result<()> {
-	toggle_profile(args.profile)
+pub fn exec_command(args: ToggleProfileArgs, zed_env: &ZedTaskEnv) -> Result<()> {
+	toggle_profile(args.profile, zed_env)
 }
 
 // region:    --- Support
 
-fn toggle_profile(target_profile: Option<String>) -> Result<()> {
+fn toggle_profile(target_profile: Option<String>, zed_env: &ZedTaskEnv) -> Result<()> {
 	let home = home_dir()?;
 
 	let config_dir = home.join(".config/jc-zed-tasks");
@@ -76,7 +76,15 @@ fn toggle_profile(target_profile: Option<String>) -> Result<()> {
 	// for now we duplicate the profile there.
 	let profile_current_spoon_path = home.join(".hammerspoon/Spoons/jc.spoon/.user/profile_current.json");
 
-	init_profiles_if_missing(&config_dir, &profiles_path, &profile_current_path)?;
+	// Serialize the toggles (quick key presses), so each one reads the profile the previous one saved
+	let _lock = files::lock_dir(&config::state_dir()?)?;
+
+	init_profiles_if_missing(&profiles_path, &profile_current_path)?;
+	if !profiles_path.exists() {
+		// only in dry-run (the initial profiles were not written)
+		println!("Would initialize the profiles in {config_dir}");
+		return Ok(());
+	}
 
 	if !settings_path.exists() {
 		return Err(format!("Zed settings file not found at: {settings_path}").into());
@@ -122,22 +130,26 @@ fn toggle_profile(target_profile: Option<String>) -> Result<()> {
 		.ok_or_else(|| format!("Profile '{next_profile_name}' not found in profiles.json"))?;
 
 	// -- Update settings.json
-	let mut settings_content = read_to_string(&settings_path)?;
-	for entry in &next_profile.zed_config {
-		let path_refs: Vec<&str> = entry.config_path.iter().map(|s| s.as_str()).collect();
-		settings_content = jsons::update_json_value_text_mode(&settings_content, &path_refs, &entry.value)?;
-	}
-	fs::write(settings_path.std_path(), settings_content)?;
+	history::edit_config_file("toggle-profile", &settings_path, |content| {
+		let mut settings_content = content.unwrap_or_default().to_string();
+		for entry in &next_profile.zed_config {
+			let path_refs: Vec<&str> = entry.config_path.iter().map(|s| s.as_str()).collect();
+			settings_content = jsons::update_json_value_text_mode(&settings_content, &path_refs, &entry.value)?;
+		}
+		Ok(settings_content)
+	})?;
 
 	// -- Update alacritty.toml
 	if !next_profile.alacritty_config.is_empty() {
 		let alacritty_path = alacritty::get_config_path()?;
-		let mut alacritty_content = fs::read_to_string(alacritty_path.std_path())?;
-		for entry in &next_profile.alacritty_config {
-			let path_refs: Vec<&str> = entry.config_path.iter().map(|s| s.as_str()).collect();
-			alacritty_content = tomls::update_toml_value_text_mode(&alacritty_content, &path_refs, &entry.value)?;
-		}
-		fs::write(alacritty_path.std_path(), alacritty_content)?;
+		history::edit_config_file("toggle-profile", &alacritty_path, |content| {
+			let mut alacritty_content = content.ok_or("alacritty.toml not found")?.to_string();
+			for entry in &next_profile.alacritty_config {
+				let path_refs: Vec<&str> = entry.config_path.iter().map(|s| s.as_str()).collect();
+				alacritty_content = tomls::update_toml_value_text_mode(&alacritty_content, &path_refs, &entry.value)?;
+			}
+			Ok(alacritty_content)
+		})?;
 	}
 
 	apply_terminal_dims(&next_profile.terminal_dims)?;
@@ -146,31 +158,32 @@ fn toggle_profile(target_profile: Option<String>) -> Result<()> {
 	let new_current = CurrentProfile {
 		current_profile: next_profile_name.clone(),
 	};
-	fs::write(
-		profile_current_path.std_path(),
-		serde_json::to_string_pretty(&new_current)?,
+	history::write_config_file(
+		"toggle-profile",
+		&profile_current_path,
+		&serde_json::to_string_pretty(&new_current)?,
 	)?;
-	// For now, we duplicate to the spoons
-	// TODO: Should only update if folder exist to be more resiliy.
-	let _ = fs::write(
-		profile_current_spoon_path.std_path(),
-		serde_json::to_string_pretty(&new_current)?,
-	);
+	// For now, we duplicate to the spoons (only if the spoon folder exists)
+	if profile_current_spoon_path.parent().is_some_and(|dir| dir.exists()) {
+		let _ = writer::write_file(&profile_current_spoon_path, serde_json::to_string_pretty(&new_current)?);
+	}
 
-	println!("Switched to profile: {next_profile_name}");
+	if writer::is_dry_run() {
+		println!("Would switch to profile: {next_profile_name}");
+	} else {
+		println!("Switched to profile: {next_profile_name}");
+	}
 
-	zed::touch_tasks_json()?;
+	zed::touch_tasks_json(zed_env)?;
 
 	Ok(())
 }
 
-fn init_profiles_if_missing(config_dir: &SPath, profiles_path: &SPath, current_path: &SPath) -> Result<()> {
+fn init_profiles_if_missing(profiles_path: &SPath, current_path: &SPath) -> Result<()> {
 	if profiles_path.exists() {
 		return Ok(());
 	}
 
-	fs::create_dir_all(config_dir.std_path())?;
-
 	let settings = zed::load_settings()?;
 	let ui_font_size = settings
 		.get("ui_font_size")
@@ -242,16 +255,21 @@ fn init_profiles_if_missing(config_dir: &SPath, profiles_path: &SPath, current_p
 		profiles,
 	};
 
-	fs::write(
-		profiles_path.std_path(),
-		serde_json::to_string_pretty(&profiles_config)?,
+	history::write_config_file(
+		"toggle-profile",
+		profiles_path,
+		&serde_json::to_string_pretty(&profiles_config)?,
 	)?;
 
 	// -- Build initial profile-current.json
 	let current_profile = CurrentProfile {
 		current_profile: "demo".to_string(),
 	};
-	fs::write(current_path.std_path(), serde_json::to_string_pretty(&current_profile)?)?;
+	history::write_config_file(
+		"toggle-profile",
+		current_path,
+		&serde_json::to_string_pretty(&current_profile)?,
+	)?;
 
 	Ok(())
 }
@@ -263,6 +281,13 @@ fn apply_terminal_dims(terminal_dims: &TerminalDims) -> Result<()> {
 	let cwd = SPath::from_std_path(&cwd)?;
 	let title = format!("zed term - {cwd}");
 
+	if !writer::side_effect(&format!(
+		"move and resize the '{title}' window to {}x{}",
+		terminal_dims.width, terminal_dims.height
+	)) {
+		return Ok(());
+	}
+
 	let _ = move_window_front_by_window_name(APP_NAME_ALACRITTY, &title);
 
 	let current_bounds = get_front_window_bounds(APP_NAME_ALACRITTY)?;
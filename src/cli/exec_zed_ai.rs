@@ -0,0 +1,45 @@
+use crate::Result;
+use crate::cli::cmd::{ZedAiArgs, ZedAiStatusArgs, ZedAiSubCmd};
+use crate::support::zed::{self, ZedTaskEnv};
+
+pub fn exec_command(args: ZedAiArgs, zed_env: &ZedTaskEnv) -> Result<()> {
+	match args.command {
+		ZedAiSubCmd::Status(args) => exec_status(args, zed_env),
+	}
+}
+
+// region:    --- Support
+
+fn exec_status(args: ZedAiStatusArgs, zed_env: &ZedTaskEnv) -> Result<()> {
+	let global_path = zed::settings_json_path()?;
+	let project_path = args
+		.worktree
+		.as_deref()
+		.or(zed_env.worktree_root.as_deref())
+		.map(zed::project_settings_json_path);
+
+	let status = zed::load_ai_status(project_path.as_ref())?;
+
+	println!("global:  {} ({global_path})", fmt_value(status.global));
+	if let Some(project_path) = project_path.as_ref() {
+		println!("project: {} ({project_path})", fmt_value(status.project));
+	}
+
+	let disabled = status.effective_disabled();
+	println!(
+		"Effective: Zed {} (disable_ai: {disabled}, from {})",
+		zed::ai_state_label(disabled),
+		status.effective_source()
+	);
+
+	Ok(())
+}
+
+fn fmt_value(disable_ai: Option<bool>) -> String {
+	match disable_ai {
+		Some(v) => format!("disable_ai: {v}"),
+		None => "disable_ai not set".to_string(),
+	}
+}
+
+// endregion: --- Support
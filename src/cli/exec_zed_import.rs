@@ -0,0 +1,132 @@
+use crate::Result;
+use crate::cli::cmd::{ZedImportArgs, ZedImportSubCmd, ZedImportVscodeArgs};
+use crate::support::{history, jsons, zed};
+use serde_json::Value;
+use simple_fs::{SPath, read_to_string};
+
+const HISTORY_COMMAND: &str = "zed-import vscode";
+
+pub fn exec_command(args: ZedImportArgs) -> Result<()> {
+	match args.command {
+		ZedImportSubCmd::Vscode(args) => exec_vscode(args),
+	}
+}
+
+// region:    --- Support
+
+fn exec_vscode(args: ZedImportVscodeArgs) -> Result<()> {
+	if args.settings.is_none() && args.keybindings.is_none() {
+		return Err("Nothing to import, use --settings <path> and/or --keybindings <path>".into());
+	}
+
+	if let Some(settings) = args.settings {
+		import_settings(&SPath::new(settings))?;
+	}
+	if let Some(keybindings) = args.keybindings {
+		import_keybindings(&SPath::new(keybindings))?;
+	}
+
+	Ok(())
+}
+
+fn import_settings(vscode_path: &SPath) -> Result<()> {
+	let vscode = load_vscode_json(vscode_path)?;
+	let import = zed::import_vscode_settings(&vscode);
+
+	let settings_path = zed::settings_json_path()?;
+	let current = if settings_path.exists() {
+		jsons::load_jsons_to_serde_value(&settings_path)?
+	} else {
+		None
+	};
+
+	println!("Settings: {vscode_path} -> {settings_path}");
+
+	// -- Keep only the changes (re-run is a no-op)
+	let mut changes = Vec::new();
+	for change in import.changes.iter() {
+		let current_value = current.as_ref().and_then(|c| value_at(c, &change.zed_path));
+		let zed_path = change.zed_path.join(".");
+		if current_value == Some(&change.value) {
+			println!(
+				"  Unchanged: {zed_path} = {}  (from {})",
+				change.value, change.vscode_key
+			);
+		} else {
+			println!(
+				"  Set:       {zed_path} = {}  (from {})",
+				change.value, change.vscode_key
+			);
+			changes.push(change);
+		}
+	}
+	for unmapped in import.unmapped.iter() {
+		println!("  Unmapped:  {}  ({})", unmapped.key, unmapped.reason);
+	}
+
+	if !changes.is_empty() {
+		history::edit_config_file(HISTORY_COMMAND, &settings_path, |content| {
+			let mut content = content.unwrap_or_default().to_string();
+			for change in changes.iter() {
+				let path_refs: Vec<&str> = change.zed_path.iter().map(|s| s.as_str()).collect();
+				content = jsons::set_value_cst(&content, &path_refs, &change.value)?;
+			}
+			Ok(content)
+		})?;
+	}
+
+	Ok(())
+}
+
+fn import_keybindings(vscode_path: &SPath) -> Result<()> {
+	let vscode = load_vscode_json(vscode_path)?;
+	let import = zed::import_vscode_keybindings(&vscode);
+
+	let keymap_path = zed::keymap_json_path()?;
+	let content = if keymap_path.exists() {
+		read_to_string(&keymap_path)?
+	} else {
+		String::new()
+	};
+
+	let (_, report) = zed::merge_keymap_bindings(&content, &import.bindings)?;
+
+	println!("Keybindings: {vscode_path} -> {keymap_path}");
+	for binding in report.added.iter() {
+		println!("  Added:     {binding}");
+	}
+	for binding in report.unchanged.iter() {
+		println!("  Unchanged: {binding}");
+	}
+	for conflict in report.conflicts.iter() {
+		let context = conflict.context.as_deref().unwrap_or("<no context>");
+		println!(
+			"  Conflict:  {} ({context}) already bound to {} (wanted {})",
+			conflict.key, conflict.existing, conflict.wanted
+		);
+	}
+	for unmapped in import.unmapped.iter() {
+		println!("  Unmapped:  {}  ({})", unmapped.key, unmapped.reason);
+	}
+
+	if report.has_changes() {
+		history::edit_config_file(HISTORY_COMMAND, &keymap_path, |content| {
+			Ok(zed::merge_keymap_bindings(content.unwrap_or_default(), &import.bindings)?.0)
+		})?;
+	}
+
+	Ok(())
+}
+
+fn load_vscode_json(path: &SPath) -> Result<Value> {
+	if !path.exists() {
+		return Err(format!("VS Code file not found at: {path}").into());
+	}
+	Ok(jsons::load_jsons_to_serde_value(path)?.unwrap_or(Value::Null))
+}
+
+fn value_at<'a>(root: &'a Value, path: &[String]) -> Option<&'a Value> {
+	path.iter().try_fold(root, |value, name| value.get(name))
+}
+
+// endregion: --- Support
@@ -0,0 +1,114 @@
+use crate::Result;
+use crate::cli::cmd::{CheatSheetFormat, ZedKeymapArgs, ZedKeymapReportArgs, ZedKeymapSubCmd};
+use crate::support::{config, history, markdown, writer, zed};
+use simple_fs::{SPath, read_to_string};
+use std::fs;
+
+pub fn exec_command(args: ZedKeymapArgs) -> Result<()> {
+	match args.command {
+		ZedKeymapSubCmd::Install(_) => exec_install(),
+		ZedKeymapSubCmd::Report(args) => exec_report(args),
+	}
+}
+
+// region:    --- Support
+
+fn exec_install() -> Result<()> {
+	let app_config = config::load_config()?;
+	if app_config.keymap.is_empty() {
+		let config_path = config::config_dir()?.join("config.json");
+		return Err(format!(
+			"No keymap bindings defined in {config_path}\n\
+			 Add for example: \"keymap\": [{{ \"context\": \"Workspace\", \"key\": \"cmd-alt-h\", \"task\": \"md-to-html\" }}]"
+		)
+		.into());
+	}
+
+	let keymap_path = zed::keymap_json_path()?;
+	let content = if keymap_path.exists() {
+		fs::read_to_string(keymap_path.std_path())?
+	} else {
+		String::new()
+	};
+
+	let (new_content, report) = zed::install_keymap_bindings(&content, &app_config.keymap)?;
+
+	// -- Report conflicts (always, also in dry-run)
+	for conflict in report.conflicts.iter() {
+		let context = conflict.context.as_deref().unwrap_or("<no context>");
+		println!(
+			"Conflict:  {} ({context}) already bound to {} (wanted {})",
+			conflict.key, conflict.existing, conflict.wanted
+		);
+	}
+
+	if writer::is_dry_run() {
+		writer::print_write_preview(&keymap_path, new_content.as_bytes())?;
+		return Ok(());
+	}
+
+	if report.has_changes() {
+		history::edit_config_file("zed-keymap install", &keymap_path, |content| {
+			Ok(zed::install_keymap_bindings(content.unwrap_or_default(), &app_config.keymap)?.0)
+		})?;
+	}
+
+	for binding in report.added.iter() {
+		println!("Added:     {binding}");
+	}
+	for binding in report.unchanged.iter() {
+		println!("Unchanged: {binding}");
+	}
+	println!("Zed keymap file: {keymap_path}");
+
+	Ok(())
+}
+
+fn exec_report(args: ZedKeymapReportArgs) -> Result<()> {
+	let keymap_path = match args.file {
+		Some(file) => SPath::new(file),
+		None => zed::keymap_json_path()?,
+	};
+	if !keymap_path.exists() {
+		return Err(format!("Zed keymap file not found at: {keymap_path}").into());
+	}
+
+	let content = read_to_string(&keymap_path)?;
+	let bindings = zed::drop_unbound_bindings(zed::parse_keymap_bindings(&content)?);
+
+	// -- Conflicts
+	let issues = zed::analyze_keymap_bindings(&bindings);
+	for issue in issues.iter() {
+		let binding = &bindings[issue.binding_idx];
+		println!(
+			"{keymap_path}:{}:{}: {}: {}",
+			binding.line,
+			binding.column,
+			issue.kind.as_str(),
+			issue.message
+		);
+	}
+	println!(
+		"{} bindings, {} issue(s) in {keymap_path}",
+		bindings.len(),
+		issues.len()
+	);
+
+	// -- Cheat sheet
+	if let Some(output) = args.output {
+		let output_path = SPath::new(output);
+		let md = zed::keymap_cheat_sheet_md(&bindings);
+		let sheet = match args.format {
+			CheatSheetFormat::Md => md,
+			CheatSheetFormat::Html => markdown::markdown_to_html(&md),
+		};
+		writer::write_file(&output_path, sheet)?;
+		if !writer::is_dry_run() {
+			println!("Cheat sheet written to {output_path}");
+		}
+	}
+
+	Ok(())
+}
+
+// endregion: --- Support
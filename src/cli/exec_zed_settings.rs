@@ -0,0 +1,163 @@
+use crate::Result;
+use crate::cli::cmd::{
+	ZedSettingsArgs, ZedSettingsEffectiveArgs, ZedSettingsFmtArgs, ZedSettingsLintArgs, ZedSettingsSubCmd,
+};
+use crate::cli::exec_toggle::ProfilesConfig;
+use crate::support::jsons_fmt::{self, FmtOptions, Indent};
+use crate::support::zed::{EffectiveSettings, ZedTaskEnv};
+use crate::support::{config, diffs, history, jsons, zed};
+use simple_fs::{SPath, read_to_string};
+
+pub fn exec_command(args: ZedSettingsArgs, zed_env: &ZedTaskEnv) -> Result<()> {
+	match args.command {
+		ZedSettingsSubCmd::Lint(args) => exec_lint(args),
+		ZedSettingsSubCmd::Fmt(args) => exec_fmt(args),
+		ZedSettingsSubCmd::Effective(args) => exec_effective(args, zed_env),
+	}
+}
+
+// region:    --- Support
+
+fn exec_lint(args: ZedSettingsLintArgs) -> Result<()> {
+	let settings_path = settings_path(args.file)?;
+
+	let content = read_to_string(&settings_path)?;
+	let profile_paths = load_profile_paths()?;
+
+	let issues = zed::lint_settings(&content, &profile_paths)?;
+	for issue in issues.iter() {
+		println!(
+			"{settings_path}:{}:{}: {}: {}",
+			issue.line,
+			issue.column,
+			issue.kind.as_str(),
+			issue.message
+		);
+	}
+
+	if issues.is_empty() {
+		println!("No issues in {settings_path}");
+		Ok(())
+	} else {
+		Err(format!("{} issue(s) found in {settings_path}", issues.len()).into())
+	}
+}
+
+fn exec_fmt(args: ZedSettingsFmtArgs) -> Result<()> {
+	let settings_path = settings_path(args.file)?;
+	let content = read_to_string(&settings_path)?;
+
+	let indent = match args.indent.as_deref() {
+		Some(indent) => Indent::from_arg(indent)?,
+		None => Indent::detect(&content),
+	};
+	let options = FmtOptions {
+		sort: args.sort.into(),
+		indent,
+		trailing_commas: args.trailing_commas.into(),
+	};
+	let formatted = jsons_fmt::format_jsonc(&content, &options)?;
+
+	if args.check {
+		if formatted == content {
+			println!("{settings_path} is formatted");
+			return Ok(());
+		}
+		let diff = diffs::unified_diff(&content, &formatted, settings_path.as_str(), settings_path.as_str());
+		print!("{diff}");
+		return Err(format!("{settings_path} is not formatted").into());
+	}
+
+	if formatted == content {
+		println!("{settings_path} already formatted");
+	} else {
+		history::edit_config_file("zed-settings fmt", &settings_path, |content| {
+			let content = content.ok_or_else(|| format!("Zed settings file not found at: {settings_path}"))?;
+			jsons_fmt::format_jsonc(content, &options)
+		})?;
+		println!("Formatted {settings_path}");
+	}
+
+	Ok(())
+}
+
+fn exec_effective(args: ZedSettingsEffectiveArgs, zed_env: &ZedTaskEnv) -> Result<()> {
+	let global_path = zed::settings_json_path()?;
+	let project_path = args
+		.worktree
+		.as_deref()
+		.or(zed_env.worktree_root.as_deref())
+		.map(zed::project_settings_json_path);
+
+	// -- Merge the layers (global, then project)
+	let mut settings = EffectiveSettings::default();
+	let mut layers = vec![("global", global_path)];
+	if let Some(project_path) = project_path {
+		layers.push(("project", project_path));
+	}
+	for (source, path) in layers {
+		if path.exists()
+			&& let Some(value) = jsons::load_jsons_to_serde_value(&path)?
+		{
+			settings.merge_layer(&value, source);
+			println!("# {source}: {path}");
+		} else {
+			println!("# {source}: {path} (not found)");
+		}
+	}
+
+	if let Some(language) = args.language.as_deref() {
+		settings.apply_language(language);
+		println!("# language: {language} (languages.{language} overrides applied)");
+	}
+
+	// -- Print
+	let entries = settings.entries(args.path.as_deref());
+	if entries.is_empty()
+		&& let Some(path) = args.path.as_deref()
+	{
+		println!("No value at '{path}' (Zed default)");
+	}
+	for entry in entries {
+		println!("{} = {}  ({})", entry.display_path(), entry.value, entry.source);
+	}
+
+	Ok(())
+}
+
+fn settings_path(file: Option<String>) -> Result<SPath> {
+	let settings_path = match file {
+		Some(file) => SPath::new(file),
+		None => zed::settings_json_path()?,
+	};
+	if !settings_path.exists() {
+		return Err(format!("Zed settings file not found at: {settings_path}").into());
+	}
+	Ok(settings_path)
+}
+
+/// The Zed settings paths updated by the profiles (empty if no profiles.json).
+fn load_profile_paths() -> Result<Vec<Vec<String>>> {
+	let profiles_path = config::config_dir()?.join("profiles.json");
+	if !profiles_path.exists() {
+		return Ok(Vec::new());
+	}
+
+	let profiles_config: ProfilesConfig = serde_json::from_str(&read_to_string(&profiles_path)?)?;
+
+	let mut paths: Vec<Vec<String>> = Vec::new();
+	for profile_name in profiles_config.order.iter() {
+		let Some(profile) = profiles_config.profiles.get(profile_name) else {
+			continue;
+		};
+		for entry in profile.zed_config.iter() {
+			if !paths.contains(&entry.config_path) {
+				paths.push(entry.config_path.clone());
+			}
+		}
+	}
+
+	Ok(paths)
+}
+
+// endregion: --- Support
@@ -0,0 +1,194 @@
+use crate::Result;
+use crate::cli::cmd::{
+	ZedSnippetAddArgs, ZedSnippetArgs, ZedSnippetImportVscodeArgs, ZedSnippetListArgs, ZedSnippetSubCmd,
+};
+use crate::support::zed::{self, ImportedSnippet, ZedTaskEnv};
+use crate::support::{history, jsons};
+use simple_fs::{SPath, list_files};
+use std::collections::BTreeMap;
+use std::fs;
+use std::io::{IsTerminal as _, Read as _};
+
+pub fn exec_command(args: ZedSnippetArgs, zed_env: &ZedTaskEnv) -> Result<()> {
+	match args.command {
+		ZedSnippetSubCmd::Add(args) => exec_add(args, zed_env),
+		ZedSnippetSubCmd::List(args) => exec_list(args),
+		ZedSnippetSubCmd::ImportVscode(args) => exec_import_vscode(args),
+	}
+}
+
+// region:    --- Support
+
+fn exec_add(args: ZedSnippetAddArgs, zed_env: &ZedTaskEnv) -> Result<()> {
+	// -- Get the text (selection, stdin, else the cursor line)
+	let text = match zed_env.selected_text.clone() {
+		Some(text) => text,
+		None => {
+			let mut stdin = std::io::stdin();
+			if stdin.is_terminal() {
+				cursor_line(zed_env)?.ok_or_else(|| {
+					format!(
+						"No text for the snippet ('{}' not set, no stdin, and no '{}' line)",
+						zed::ZED_SELECTED_TEXT,
+						zed::ZED_ROW
+					)
+				})?
+			} else {
+				let mut text = String::new();
+				stdin.read_to_string(&mut text)?;
+				text
+			}
+		}
+	};
+	if text.trim().is_empty() {
+		return Err("Cannot add a snippet with an empty body".into());
+	}
+
+	// -- Add the snippet
+	let snippets_path = zed::snippets_file_path(&args.lang)?;
+	let name = args.name.as_deref().unwrap_or(&args.prefix);
+	let body = zed::snippet_body_from_text(&text);
+	let prefixes = [args.prefix.clone()];
+
+	history::edit_config_file("zed-snippet add", &snippets_path, |content| {
+		zed::add_snippet(
+			content.unwrap_or_default(),
+			name,
+			&prefixes,
+			&body,
+			args.description.as_deref(),
+		)
+	})?;
+
+	println!("Snippet '{name}' (prefix '{}') added to {snippets_path}", args.prefix);
+
+	Ok(())
+}
+
+/// The `ZED_FILE` line at `ZED_ROW` (when both are set).
+fn cursor_line(zed_env: &ZedTaskEnv) -> Result<Option<String>> {
+	let (Some(file), Some(row)) = (zed_env.file.as_deref(), zed_env.row()?) else {
+		return Ok(None);
+	};
+	let content = fs::read_to_string(file)?;
+	let line = content
+		.lines()
+		.nth(row.saturating_sub(1) as usize)
+		.map(|line| line.to_string());
+	Ok(line)
+}
+
+fn exec_list(args: ZedSnippetListArgs) -> Result<()> {
+	let snippet_files: Vec<SPath> = match args.lang.as_deref() {
+		Some(lang) => vec![zed::snippets_file_path(lang)?],
+		None => {
+			let snippets_dir = zed::snippets_dir()?;
+			if !snippets_dir.exists() {
+				return Err(format!("Zed snippets directory not found at: {snippets_dir}").into());
+			}
+			list_files(&snippets_dir, Some(&["*.json"]), None)?
+		}
+	};
+
+	for file in snippet_files {
+		if !file.exists() {
+			return Err(format!("Zed snippets file not found at: {file}").into());
+		}
+		let content = fs::read_to_string(file.std_path())?;
+		let snippets = zed::list_snippets(&content)?;
+
+		println!("{} ({} snippets)", file.stem(), snippets.len());
+		for snippet in snippets {
+			let prefixes = snippet.prefixes.join(", ");
+			match snippet.description {
+				Some(description) => println!("  {prefixes:<16} {} - {description}", snippet.name),
+				None => println!("  {prefixes:<16} {}", snippet.name),
+			}
+		}
+	}
+
+	Ok(())
+}
+
+fn exec_import_vscode(args: ZedSnippetImportVscodeArgs) -> Result<()> {
+	let vscode_path = SPath::new(args.file);
+	if !vscode_path.exists() {
+		return Err(format!("VS Code snippet file not found at: {vscode_path}").into());
+	}
+	let vscode = jsons::load_jsons_to_serde_value(&vscode_path)?.unwrap_or_default();
+
+	// `snippets/<lang>.json` are for one language, `.code-snippets` have a `scope` per snippet
+	let default_lang = match args.lang {
+		Some(lang) => Some(lang),
+		None if vscode_path.ext() == "json" => Some(vscode_path.stem().to_string()),
+		None => None,
+	};
+	let import = zed::import_vscode_snippets(&vscode, default_lang.as_deref());
+
+	// -- Merge per snippet file
+	let mut by_lang: BTreeMap<&str, Vec<&ImportedSnippet>> = BTreeMap::new();
+	for snippet in import.snippets.iter() {
+		by_lang.entry(snippet.lang.as_str()).or_default().push(snippet);
+	}
+
+	for (lang, snippets) in by_lang {
+		let snippets_path = zed::snippets_file_path(lang)?;
+		let content = if snippets_path.exists() {
+			fs::read_to_string(snippets_path.std_path())?
+		} else {
+			String::new()
+		};
+		let (new_content, lines) = add_snippets(&content, lang, &snippets)?;
+		for line in lines {
+			println!("{line}");
+		}
+
+		if new_content != content {
+			history::edit_config_file("zed-snippet import-vscode", &snippets_path, |content| {
+				Ok(add_snippets(content.unwrap_or_default(), lang, &snippets)?.0)
+			})?;
+		}
+	}
+
+	for (name, reason) in import.skipped.iter() {
+		println!("Skipped: {name} ({reason})");
+	}
+
+	Ok(())
+}
+
+/// Add the snippets to a snippet file content (the ones already there are skipped).
+/// Returns the new content and the report lines.
+fn add_snippets(content: &str, lang: &str, snippets: &[&ImportedSnippet]) -> Result<(String, Vec<String>)> {
+	// fail on a broken file (so the add errors below are only the duplicates)
+	zed::list_snippets(content)?;
+
+	let mut new_content = content.to_string();
+	let mut lines = Vec::new();
+	for snippet in snippets {
+		let prefixes = snippet.prefixes.join(", ");
+		match zed::add_snippet(
+			&new_content,
+			&snippet.name,
+			&snippet.prefixes,
+			&snippet.body,
+			snippet.description.as_deref(),
+		) {
+			Ok(added) => {
+				new_content = added;
+				lines.push(format!("Added:   {lang:<12} {prefixes:<16} {}", snippet.name));
+				for variable in snippet.variables.iter() {
+					lines.push(format!(
+						"         {:<12} {:<16} variable {variable} converted to a placeholder",
+						"", ""
+					));
+				}
+			}
+			Err(err) => lines.push(format!("Skipped: {lang:<12} {prefixes:<16} {err}")),
+		}
+	}
+
+	Ok((new_content, lines))
+}
+
+// endregion: --- Support
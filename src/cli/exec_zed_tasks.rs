@@ -0,0 +1,65 @@
+use crate::Result;
+use crate::cli::cmd::{ZedTasksArgs, ZedTasksInstallArgs, ZedTasksSubCmd};
+use crate::support::zed::{self, TASK_CATALOG, TasksMergeReport};
+use crate::support::{history, writer};
+use std::fs;
+
+pub fn exec_command(args: ZedTasksArgs) -> Result<()> {
+	match args.command {
+		ZedTasksSubCmd::Install(args) => exec_install(args),
+	}
+}
+
+// region:    --- Support
+
+fn exec_install(args: ZedTasksInstallArgs) -> Result<()> {
+	let tasks_path = zed::tasks_json_path()?;
+
+	let content = if tasks_path.exists() {
+		fs::read_to_string(tasks_path.std_path())?
+	} else {
+		String::new()
+	};
+
+	let merge = |content: &str| {
+		if args.uninstall {
+			zed::uninstall_tasks(content, TASK_CATALOG)
+		} else {
+			zed::install_tasks(content, TASK_CATALOG)
+		}
+	};
+	let (new_content, report) = merge(&content)?;
+
+	if writer::is_dry_run() {
+		writer::print_write_preview(&tasks_path, new_content.as_bytes())?;
+		return Ok(());
+	}
+
+	if report.has_changes() {
+		history::edit_config_file("zed-tasks install", &tasks_path, |content| {
+			Ok(merge(content.unwrap_or_default())?.0)
+		})?;
+	}
+
+	print_report(&report);
+	println!("Zed tasks file: {tasks_path}");
+
+	Ok(())
+}
+
+fn print_report(report: &TasksMergeReport) {
+	for label in report.added.iter() {
+		println!("Added:     {label}");
+	}
+	for label in report.updated.iter() {
+		println!("Updated:   {label}");
+	}
+	for label in report.unchanged.iter() {
+		println!("Unchanged: {label}");
+	}
+	for label in report.removed.iter() {
+		println!("Removed:   {label}");
+	}
+}
+
+// endregion: --- Support
@@ -0,0 +1,178 @@
+use crate::Result;
+use crate::cli::cmd::{ZedThemeArgs, ZedThemeSubCmd};
+use crate::support::config::{self, ZedThemeConfig};
+use crate::support::hash::fnv1a_64_hex;
+use crate::support::zed::{self, THEME_KEY, ThemeSetting};
+use crate::support::{alacritty, history, jsons};
+use simple_fs::SPath;
+use std::fs;
+
+pub fn exec_command(args: ZedThemeArgs) -> Result<()> {
+	let theme_config = config::load_config()?.zed_theme;
+
+	let settings_path = zed::settings_json_path()?;
+	if !settings_path.exists() {
+		return Err(format!("Zed settings file not found at: {settings_path}").into());
+	}
+	let current = current_theme(&fs::read_to_string(settings_path.std_path())?)?;
+
+	// -- Compute the new theme setting
+	let new_setting = match args.command {
+		ZedThemeSubCmd::Next => {
+			let name = zed::cycle_theme(&theme_config.list, current.active(), 1)?;
+			current.clone().with_active(name)
+		}
+		ZedThemeSubCmd::Prev => {
+			let name = zed::cycle_theme(&theme_config.list, current.active(), -1)?;
+			current.clone().with_active(name)
+		}
+		ZedThemeSubCmd::Set(set_args) => {
+			let setting = current.clone().with_active(set_args.name);
+			if set_args.plain { setting.into_plain() } else { setting }
+		}
+		ZedThemeSubCmd::Mode(mode_args) => current.clone().with_mode(
+			mode_args.mode.into(),
+			theme_config.light.as_deref(),
+			theme_config.dark.as_deref(),
+		),
+	};
+
+	// -- Check the Alacritty sync before writing anything
+	let alacritty_sync = if args.no_alacritty {
+		None
+	} else {
+		alacritty_sync(&theme_config, new_setting.active())?
+	};
+
+	// -- Write settings.json, then alacritty.toml (restoring settings.json if it fails)
+	// (before, after) contents of the settings.json write
+	let mut written: Option<(String, String)> = None;
+	if new_setting != current {
+		history::edit_config_file("zed-theme", &settings_path, |content| {
+			let content = content.ok_or_else(|| format!("Zed settings file not found at: {settings_path}"))?;
+			let new_content = set_theme_content(content, &new_setting)?;
+			written = Some((content.to_string(), new_content.clone()));
+			Ok(new_content)
+		})?;
+	}
+	if let Some(sync) = alacritty_sync.as_ref()
+		&& let Err(err) = sync.apply()
+	{
+		let restored = match written.as_ref() {
+			Some((before, after)) => restore_settings(&settings_path, after, before)?,
+			None => true,
+		};
+		let status = if restored {
+			"settings.json restored"
+		} else {
+			"settings.json changed since, not restored"
+		};
+		return Err(format!("Cannot sync the Alacritty colors ({status}). Cause: {err}").into());
+	}
+
+	match &new_setting {
+		ThemeSetting::Plain(name) => println!("Zed theme: {name}"),
+		ThemeSetting::Modal { mode, light, dark } => {
+			println!(
+				"Zed theme: {} (mode: {}, light: {light}, dark: {dark})",
+				new_setting.active(),
+				mode.as_str()
+			)
+		}
+	}
+
+	if let Some(sync) = alacritty_sync {
+		println!("Alacritty colors: {}", sync.new_import);
+	}
+
+	Ok(())
+}
+
+// region:    --- Support
+
+/// The theme setting of the settings.json content (Zed default when not set).
+fn current_theme(content: &str) -> Result<ThemeSetting> {
+	let settings = jsons::parse_jsonc_to_serde_value(content)?.unwrap_or_default();
+	match settings.get(THEME_KEY) {
+		Some(value) => ThemeSetting::from_value(value),
+		None => Ok(ThemeSetting::Plain("One Dark".to_string())),
+	}
+}
+
+/// Set the theme setting in the settings.json content.
+fn set_theme_content(content: &str, new_setting: &ThemeSetting) -> Result<String> {
+	let current = current_theme(content)?;
+	match (&current, new_setting) {
+		// Update the changed props only, to keep the comments of the theme object
+		(ThemeSetting::Modal { .. }, ThemeSetting::Modal { .. }) => {
+			let (current_value, new_value) = (current.to_value(), new_setting.to_value());
+			let mut new_content = content.to_string();
+			for prop in ["mode", "light", "dark"] {
+				if current_value.get(prop) != new_value.get(prop)
+					&& let Some(value) = new_value.get(prop)
+				{
+					new_content = jsons::set_value_cst(&new_content, &[THEME_KEY, prop], value)?;
+				}
+			}
+			Ok(new_content)
+		}
+		_ => jsons::set_value_cst(content, &[THEME_KEY], &new_setting.to_value()),
+	}
+}
+
+/// Restore settings.json to its `before` content, only if it still has the `written` content
+/// (so a change made meanwhile, e.g., by Zed, is left alone). Returns whether it was restored.
+fn restore_settings(settings_path: &SPath, written: &str, before: &str) -> Result<bool> {
+	if !settings_path.exists() {
+		return Ok(false);
+	}
+	let written_hash = fnv1a_64_hex(written.as_bytes());
+	let mut restored = false;
+	history::edit_config_file("zed-theme", settings_path, |content| {
+		restored = content.is_some_and(|c| fnv1a_64_hex(c.as_bytes()) == written_hash);
+		match content {
+			Some(_) if restored => Ok(before.to_string()),
+			Some(content) => Ok(content.to_string()),
+			None => Err("settings.json not found".into()),
+		}
+	})?;
+
+	Ok(restored)
+}
+
+/// The alacritty.toml colors import switch of a theme.
+struct AlacrittySync {
+	path: SPath,
+	candidates: Vec<String>,
+	new_import: String,
+}
+
+impl AlacrittySync {
+	fn apply(&self) -> Result<()> {
+		let candidates: Vec<&str> = self.candidates.iter().map(|s| s.as_str()).collect();
+		history::edit_config_file("zed-theme", &self.path, |content| {
+			let content = content.ok_or("alacritty.toml not found")?;
+			alacritty::switch_import_text_mode(content, &candidates, &self.new_import)
+		})
+	}
+}
+
+/// The alacritty.toml colors import switch when the theme has one in the config,
+/// checked on the current alacritty.toml (so a failing sync is reported before any write).
+fn alacritty_sync(theme_config: &ZedThemeConfig, theme_name: &str) -> Result<Option<AlacrittySync>> {
+	let Some(new_import) = theme_config.alacritty_colors.get(theme_name) else {
+		return Ok(None);
+	};
+
+	let path = alacritty::get_config_path()?;
+	let candidates: Vec<&str> = theme_config.alacritty_colors.values().map(|s| s.as_str()).collect();
+	alacritty::switch_import_text_mode(&fs::read_to_string(path.std_path())?, &candidates, new_import)?;
+
+	Ok(Some(AlacrittySync {
+		path,
+		candidates: candidates.iter().map(|s| s.to_string()).collect(),
+		new_import: new_import.clone(),
+	}))
+}
+
+// endregion: --- Support
@@ -1,21 +1,37 @@
 use crate::Result;
-use crate::cli::cmd::{CliCmd, CliSubCmd, MdToHtmlArgs, SaveClipboardImageArgs, TmuxRunAipArgs};
-use crate::cli::exec_toggle;
-use crate::support::{clipboard, jsons, tmux, zed};
+use crate::cli::cmd::{CliCmd, CliSubCmd, MdToHtmlArgs, TmuxRunAipArgs, ZedToggleAiArgs};
+use crate::cli::{
+	exec_clipboard, exec_history, exec_images, exec_save_image, exec_theme, exec_toggle, exec_zed_ai, exec_zed_import,
+	exec_zed_keymap, exec_zed_settings, exec_zed_snippet, exec_zed_tasks, exec_zed_theme,
+};
+use crate::support::zed::{self, ZedTaskEnv};
+use crate::support::{history, jsons, markdown, tmux, writer};
 use clap::Parser as _;
-use lazy_regex::regex;
-use simple_fs::{SPath, list_files, read_to_string};
-use std::fs;
+use simple_fs::{SPath, read_to_string};
 
 pub fn execute() -> Result<()> {
 	let cli_cmd = CliCmd::parse();
+	writer::set_dry_run(cli_cmd.dry_run);
+	let zed_env = ZedTaskEnv::from_env();
 
 	match cli_cmd.command {
-		CliSubCmd::TmuxRunAip(args) => exec_tmux_run_aip(args)?,
-		CliSubCmd::ZedToggleAi => exec_zed_toggle_ai()?,
-		CliSubCmd::ToggleProfile(args) => exec_toggle::exec_command(args)?,
-		CliSubCmd::SaveClipboardImage(args) => exec_save_clipboard_image(args)?,
-		CliSubCmd::MdToHtml(args) => exec_md_to_html(args)?,
+		CliSubCmd::TmuxRunAip(args) => exec_tmux_run_aip(args, &zed_env)?,
+		CliSubCmd::ZedToggleAi(args) => exec_zed_toggle_ai(args, &zed_env)?,
+		CliSubCmd::ZedAi(args) => exec_zed_ai::exec_command(args, &zed_env)?,
+		CliSubCmd::ToggleProfile(args) => exec_toggle::exec_command(args, &zed_env)?,
+		CliSubCmd::SaveClipboardImage(args) => exec_save_image::exec_command(args, &zed_env)?,
+		CliSubCmd::MdToHtml(args) => exec_md_to_html(args, &zed_env)?,
+		CliSubCmd::Clipboard(args) => exec_clipboard::exec_command(args)?,
+		CliSubCmd::Images(args) => exec_images::exec_command(args, &zed_env)?,
+		CliSubCmd::ZedTasks(args) => exec_zed_tasks::exec_command(args)?,
+		CliSubCmd::ZedKeymap(args) => exec_zed_keymap::exec_command(args)?,
+		CliSubCmd::ZedSnippet(args) => exec_zed_snippet::exec_command(args, &zed_env)?,
+		CliSubCmd::ZedTheme(args) => exec_zed_theme::exec_command(args)?,
+		CliSubCmd::ZedSettings(args) => exec_zed_settings::exec_command(args, &zed_env)?,
+		CliSubCmd::ZedImport(args) => exec_zed_import::exec_command(args)?,
+		CliSubCmd::Theme(args) => exec_theme::exec_command(args)?,
+		CliSubCmd::History(args) => exec_history::exec_command(args)?,
+		CliSubCmd::Undo(args) => exec_history::exec_undo(args)?,
 	}
 
 	Ok(())
@@ -23,75 +39,26 @@ pub fn execute() -> Result<()> {
 
 // region:    --- Exec Handlers
 
-fn exec_save_clipboard_image(args: SaveClipboardImageArgs) -> Result<()> {
-	zed::touch_tasks_json()?;
+fn exec_md_to_html(args: MdToHtmlArgs, zed_env: &ZedTaskEnv) -> Result<()> {
+	zed::touch_tasks_json(zed_env)?;
 
-	let dir = SPath::new(args.dir);
-	if !dir.exists() {
-		return Err(format!("Directory does not exist: {dir}").into());
-	}
-
-	let re = regex!(r"^image-(\d+)\.png$");
-	let mut max_idx = 0;
-
-	// -- Find max index
-	let files = list_files(&dir, Some(&["image-*.png"]), None)?;
-	for file in files {
-		if let Some(caps) = re.captures(file.name())
-			&& let Ok(idx) = caps[1].parse::<u32>()
-			&& idx > max_idx
-		{
-			max_idx = idx;
-		}
-	}
-
-	// -- Save image
-	let next_idx = max_idx + 1;
-	let file_name = format!("image-{:02}.png", next_idx);
-	let dest_path = dir.join(&file_name);
-
-	clipboard::save_to_png_image(&dest_path)?;
-
-	if args.copy_md_ref {
-		let md_ref = format!("![IMAGE]({file_name})");
-		clipboard::set_text(md_ref)?;
-		println!("Markdown reference copied to clipboard: {file_name}");
-	}
-
-	println!("Image saved to: {dest_path}");
-
-	Ok(())
-}
-
-fn exec_md_to_html(args: MdToHtmlArgs) -> Result<()> {
-	zed::touch_tasks_json()?;
-
-	let md_path = SPath::new(args.file);
+	let md_path = SPath::new(args.file(zed_env)?);
 	let content = read_to_string(&md_path)?;
 
-	let mut options = pulldown_cmark::Options::empty();
-	options.insert(pulldown_cmark::Options::ENABLE_TABLES);
-	options.insert(pulldown_cmark::Options::ENABLE_FOOTNOTES);
-	options.insert(pulldown_cmark::Options::ENABLE_STRIKETHROUGH);
-	options.insert(pulldown_cmark::Options::ENABLE_TASKLISTS);
-	options.insert(pulldown_cmark::Options::ENABLE_SMART_PUNCTUATION);
-
-	let parser = pulldown_cmark::Parser::new_ext(&content, options);
-
-	let mut html_output = String::new();
-	pulldown_cmark::html::push_html(&mut html_output, parser);
+	let html_output = markdown::markdown_to_html(&content);
 
 	let html_path = md_path.ensure_extension("html");
-	fs::write(html_path.std_path(), html_output)?;
+	writer::write_file(&html_path, html_output)?;
 
-	println!("Converted {md_path} to {html_path}");
+	if !writer::is_dry_run() {
+		println!("Converted {md_path} to {html_path}");
+	}
 
 	Ok(())
 }
 
-fn exec_tmux_run_aip(args: TmuxRunAipArgs) -> Result<()> {
-	let dir_str = args.dir.as_deref().ok_or("tmux_run_aip must have a --dir")?;
-	let dir = SPath::new(dir_str);
+fn exec_tmux_run_aip(args: TmuxRunAipArgs, zed_env: &ZedTaskEnv) -> Result<()> {
+	let dir = SPath::new(args.dir(zed_env)?);
 
 	let pane_id = if let Some(pane_name) = args.pane.as_deref() {
 		let pane = tmux::find_first_pane(Some(&dir), Some(pane_name))?;
@@ -127,14 +94,19 @@ fn exec_tmux_run_aip(args: TmuxRunAipArgs) -> Result<()> {
 		aip_pane.id.clone()
 	};
 
-	tmux::send_keys(&pane_id, "r")?;
+	if writer::side_effect(&format!("send 'r' to tmux pane {pane_id}")) {
+		tmux::send_keys(&pane_id, "r")?;
+	}
 
 	Ok(())
 }
 
-fn exec_zed_toggle_ai() -> Result<()> {
-	let home = home::home_dir().ok_or("Could not find home directory")?;
-	let settings_path = SPath::from_std_path(home)?.join(".config/zed/settings.json");
+fn exec_zed_toggle_ai(args: ZedToggleAiArgs, zed_env: &ZedTaskEnv) -> Result<()> {
+	if args.project {
+		return exec_zed_toggle_ai_project(args, zed_env);
+	}
+
+	let settings_path = zed::settings_json_path()?;
 
 	if !settings_path.exists() {
 		return Err(crate::Error::custom(format!(
@@ -142,14 +114,67 @@ fn exec_zed_toggle_ai() -> Result<()> {
 		)));
 	}
 
-	let content = simple_fs::read_to_string(&settings_path)?;
-	let new_content = jsons::toggle_bool_text_mode(&content, &["disable_ai"])?;
+	let mut disabled = false;
+	history::edit_config_file("zed-toggle-ai", &settings_path, |content| {
+		let new_content = jsons::toggle_bool_text_mode(content.unwrap_or_default(), &[zed::DISABLE_AI_KEY])?;
+		disabled = jsons::parse_jsonc_to_serde_value(&new_content)?
+			.as_ref()
+			.and_then(|v| v.get(zed::DISABLE_AI_KEY))
+			.and_then(|v| v.as_bool())
+			.unwrap_or(false);
+		Ok(new_content)
+	})?;
+
+	println!(
+		"{}Zed {} (disable_ai: {disabled}) in {settings_path}",
+		would_prefix(),
+		zed::ai_state_label(disabled)
+	);
+
+	// -- Warn when the project settings override it
+	if let Some(worktree_root) = zed_env.worktree_root.as_deref() {
+		let project_path = zed::project_settings_json_path(worktree_root);
+		if let Some(project_disabled) = zed::read_disable_ai(&project_path)?
+			&& project_disabled != disabled
+		{
+			println!(
+				"Note: overridden by project settings ({}) in {project_path}",
+				zed::ai_state_label(project_disabled)
+			);
+		}
+	}
 
-	fs::write(settings_path.std_path(), new_content)?;
+	Ok(())
+}
 
-	println!("Zed AI toggled.");
+/// Toggle the effective `disable_ai` value in the project settings (created if missing).
+fn exec_zed_toggle_ai_project(args: ZedToggleAiArgs, zed_env: &ZedTaskEnv) -> Result<()> {
+	let worktree_root = args.worktree(zed_env)?;
+	let settings_path = zed::project_settings_json_path(&worktree_root);
+
+	let mut disabled = false;
+	history::edit_config_file("zed-toggle-ai --project", &settings_path, |content| {
+		let status = zed::load_ai_status(Some(&settings_path))?;
+		disabled = !status.effective_disabled();
+		jsons::set_value_cst(
+			content.unwrap_or_default(),
+			&[zed::DISABLE_AI_KEY],
+			&serde_json::Value::Bool(disabled),
+		)
+	})?;
+
+	println!(
+		"{}Zed {} (disable_ai: {disabled}) in project settings {settings_path}",
+		would_prefix(),
+		zed::ai_state_label(disabled)
+	);
 
 	Ok(())
 }
 
+/// The "Would switch to " prefix of the result messages in dry-run mode.
+fn would_prefix() -> &'static str {
+	if writer::is_dry_run() { "Would switch to " } else { "" }
+}
+
 // endregion: --- Exec Handlers
@@ -1,7 +1,19 @@
 // region:    --- Modules
 
 mod cmd;
+mod exec_clipboard;
+mod exec_history;
+mod exec_images;
+mod exec_save_image;
+mod exec_theme;
 mod exec_toggle;
+mod exec_zed_ai;
+mod exec_zed_import;
+mod exec_zed_keymap;
+mod exec_zed_settings;
+mod exec_zed_snippet;
+mod exec_zed_tasks;
+mod exec_zed_theme;
 mod executor;
 
 pub use executor::execute;
@@ -1,6 +1,8 @@
 use crate::Result;
 use crate::support::tomls;
+use lazy_regex::regex;
 use simple_fs::SPath;
+use std::ops::Range;
 
 pub fn load_settings() -> Result<serde_json::Value> {
 	let settings_path = get_config_path()?;
@@ -31,3 +33,107 @@ pub fn get_config_path() -> Result<SPath> {
 		"Alacritty config file not found (checked ~/.config/alacritty/alacritty.toml, etc.)",
 	))
 }
+
+/// Switch the colors import of the alacritty.toml content (text mode, so comments and format are preserved).
+/// Replaces the quoted `candidates` import paths by `new_import` (paths are compared as written in the file),
+/// only in the `import = [...]` array (e.g., not in a comment or another setting).
+pub fn switch_import_text_mode(content: &str, candidates: &[&str], new_import: &str) -> Result<String> {
+	let not_found = || {
+		crate::Error::custom(format!(
+			"No known colors import found in alacritty.toml (expected one of: {})",
+			candidates.join(", ")
+		))
+	};
+	let import_span = import_array_span(content).ok_or_else(not_found)?;
+	let imports = &content[import_span.clone()];
+
+	let new_quoted = format!(r#""{new_import}""#);
+	let mut new_imports = imports.to_string();
+	let mut found = new_imports.contains(&new_quoted);
+	for candidate in candidates.iter().filter(|c| **c != new_import) {
+		let quoted = format!(r#""{candidate}""#);
+		if new_imports.contains(&quoted) {
+			new_imports = new_imports.replace(&quoted, &new_quoted);
+			found = true;
+		}
+	}
+	if !found {
+		return Err(not_found());
+	}
+
+	Ok(format!(
+		"{}{new_imports}{}",
+		&content[..import_span.start],
+		&content[import_span.end..]
+	))
+}
+
+// region:    --- Support
+
+/// The byte range of the `import = [...]` array (from `[` to `]`), skipping the `]` in quoted strings and comments.
+fn import_array_span(content: &str) -> Option<Range<usize>> {
+	let start = regex!(r"(?m)^[ \t]*import[ \t]*=[ \t]*\[").find(content)?.end() - 1;
+
+	let mut in_string = false;
+	let mut in_comment = false;
+	for (idx, c) in content[start..].char_indices() {
+		match c {
+			'\n' => in_comment = false,
+			_ if in_comment => {}
+			'"' => in_string = !in_string,
+			'#' if !in_string => in_comment = true,
+			']' if !in_string => return Some(start..start + idx + 1),
+			_ => {}
+		}
+	}
+	None
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_support_alacritty_switch_import_text_mode_only_import_array() -> Result<()> {
+		// -- Setup & Fixtures
+		let content = r#"# previous: "~/themes/dark.toml"
+[general]
+import = [
+  "~/themes/dark.toml", # colors ]
+  "~/keys.toml",
+]
+
+[shell]
+args = ["~/themes/dark.toml"]
+"#;
+
+		// -- Exec
+		let new_content = switch_import_text_mode(
+			content,
+			&["~/themes/dark.toml", "~/themes/light.toml"],
+			"~/themes/light.toml",
+		)?;
+		let no_import = switch_import_text_mode(
+			"[shell]\nargs = [\"~/themes/dark.toml\"]\n",
+			&["~/themes/dark.toml"],
+			"~/x.toml",
+		);
+
+		// -- Check
+		assert_eq!(
+			new_content,
+			content.replacen(r#""~/themes/dark.toml", #"#, r#""~/themes/light.toml", #"#, 1)
+		);
+		assert!(no_import.is_err());
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
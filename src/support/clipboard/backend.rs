@@ -0,0 +1,120 @@
+use crate::Result;
+use crate::support::clipboard::{
+	ArboardBackend, ClipboardBackend, ClipboardKind, ClipboardTool, CommandBackend, FileBackend,
+};
+use crate::support::config;
+use simple_fs::SPath;
+
+/// Env var to choose the clipboard backend (overrides the config `clipboard.backend`).
+/// Values: `auto` (default), `arboard`, `wl-clipboard`, `xclip`, `file:<dir>`.
+pub const CLIPBOARD_BACKEND_ENV: &str = "JC_ZED_TASKS_CLIPBOARD";
+
+/// Returns the clipboard backend from the env, else the config, else the first available one.
+pub fn backend() -> Result<Box<dyn ClipboardBackend>> {
+	let spec = match std::env::var(CLIPBOARD_BACKEND_ENV).ok().filter(|v| !v.is_empty()) {
+		Some(spec) => spec,
+		None => config::load_config()?
+			.clipboard
+			.backend
+			.unwrap_or_else(|| "auto".to_string()),
+	};
+	backend_from_spec(&spec)
+}
+
+/// Returns the current image from the clipboard encoded as PNG.
+pub fn get_image_as_png() -> Result<Vec<u8>> {
+	backend()?.read(ClipboardKind::Image)
+}
+
+/// Sets the specified text to the clipboard.
+pub fn set_text(text: impl Into<String>) -> Result<()> {
+	backend()?.write(ClipboardKind::Text, text.into().as_bytes())
+}
+
+// region:    --- Support
+
+fn backend_from_spec(spec: &str) -> Result<Box<dyn ClipboardBackend>> {
+	let backend: Box<dyn ClipboardBackend> = match spec.trim() {
+		"auto" => auto_backend()?,
+		"arboard" => Box::new(ArboardBackend::new()?),
+		"wl-clipboard" => Box::new(CommandBackend::new(ClipboardTool::WlClipboard)),
+		"xclip" => Box::new(CommandBackend::new(ClipboardTool::Xclip)),
+		other => match other.strip_prefix("file:").filter(|dir| !dir.is_empty()) {
+			Some(dir) => Box::new(FileBackend::new(SPath::new(dir))),
+			None => {
+				return Err(format!(
+					"Invalid clipboard backend '{other}' (expected auto, arboard, wl-clipboard, xclip, or file:<dir>)"
+				)
+				.into());
+			}
+		},
+	};
+	Ok(backend)
+}
+
+/// On Wayland, wl-clipboard first (arboard is X11 only there), then arboard, then xclip.
+fn auto_backend() -> Result<Box<dyn ClipboardBackend>> {
+	let is_wayland = std::env::var("WAYLAND_DISPLAY").is_ok_and(|v| !v.is_empty());
+	if cfg!(target_os = "linux") && is_wayland && CommandBackend::is_available(ClipboardTool::WlClipboard) {
+		return Ok(Box::new(CommandBackend::new(ClipboardTool::WlClipboard)));
+	}
+
+	let arboard_err = match ArboardBackend::new() {
+		Ok(backend) => return Ok(Box::new(backend)),
+		Err(err) => err,
+	};
+
+	if cfg!(target_os = "linux") && CommandBackend::is_available(ClipboardTool::Xclip) {
+		return Ok(Box::new(CommandBackend::new(ClipboardTool::Xclip)));
+	}
+
+	Err(format!(
+		"No clipboard backend available ({arboard_err}, and no wl-clipboard or xclip).\n\
+		 Set {CLIPBOARD_BACKEND_ENV}=file:<dir> for a file clipboard."
+	)
+	.into())
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+	use crate::support::clipboard::{paths_to_uri_list, uri_list_to_paths};
+	use std::path::PathBuf;
+
+	#[test]
+	fn test_support_clipboard_backend_file_roundtrip() -> Result<()> {
+		// -- Setup & Fixtures
+		let dir = std::env::temp_dir().join(format!("jc-zed-tasks-test-clipboard-{}", std::process::id()));
+		let spec = format!("file:{}", dir.display());
+		let paths = vec![PathBuf::from("/tmp/my notes/a.md"), PathBuf::from("/tmp/b.png")];
+
+		// -- Exec
+		let mut backend = backend_from_spec(&spec)?;
+		backend.write(ClipboardKind::Html, b"<b>hi</b>")?;
+		let html = backend.read(ClipboardKind::Html)?;
+		backend.write(ClipboardKind::UriList, paths_to_uri_list(&paths).as_bytes())?;
+		let uri_list = String::from_utf8(backend.read(ClipboardKind::UriList)?)?;
+		let html_after = backend.read(ClipboardKind::Html);
+
+		// -- Check
+		assert_eq!(backend.name(), "file");
+		assert_eq!(html, b"<b>hi</b>");
+		assert_eq!(uri_list, "file:///tmp/my%20notes/a.md\r\nfile:///tmp/b.png");
+		assert_eq!(uri_list_to_paths(&uri_list)?, paths);
+		assert!(html_after.is_err(), "a write should replace the other kinds");
+		assert!(backend_from_spec("clipboard-of-mars").is_err());
+
+		// -- Cleanup
+		std::fs::remove_dir_all(&dir)?;
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,77 @@
+use crate::Result;
+use crate::support::clipboard::{ClipboardBackend, ClipboardKind, paths_to_uri_list, uri_list_to_paths};
+use arboard::{Clipboard, ImageData};
+use image::ImageEncoder as _;
+use std::borrow::Cow;
+
+/// The system clipboard through the `arboard` crate (macOS, Windows, X11).
+pub struct ArboardBackend {
+	clipboard: Clipboard,
+}
+
+impl ArboardBackend {
+	pub fn new() -> Result<Self> {
+		let clipboard = Clipboard::new().map_err(|e| format!("Could not initialize clipboard: {e}"))?;
+		Ok(Self { clipboard })
+	}
+}
+
+impl ClipboardBackend for ArboardBackend {
+	fn name(&self) -> &'static str {
+		"arboard"
+	}
+
+	fn read(&mut self, kind: ClipboardKind) -> Result<Vec<u8>> {
+		let get = self.clipboard.get();
+		let res = match kind {
+			ClipboardKind::Image => get.image().map(|image| encode_png(&image)),
+			ClipboardKind::Text => get.text().map(|text| Ok(text.into_bytes())),
+			ClipboardKind::Html => get.html().map(|html| Ok(html.into_bytes())),
+			ClipboardKind::UriList => get.file_list().map(|paths| Ok(paths_to_uri_list(&paths).into_bytes())),
+		};
+
+		res.map_err(|e| format!("Could not get {} from clipboard. Cause: {e}", kind.as_str()))?
+	}
+
+	fn write(&mut self, kind: ClipboardKind, data: &[u8]) -> Result<()> {
+		let set = self.clipboard.set();
+		let res = match kind {
+			ClipboardKind::Image => {
+				let image = image::load_from_memory_with_format(data, image::ImageFormat::Png)
+					.map_err(|e| format!("Clipboard image is not a valid PNG. Cause: {e}"))?
+					.to_rgba8();
+				set.image(ImageData {
+					width: image.width() as usize,
+					height: image.height() as usize,
+					bytes: Cow::Owned(image.into_raw()),
+				})
+			}
+			ClipboardKind::Text => set.text(String::from_utf8_lossy(data)),
+			ClipboardKind::Html => set.html(String::from_utf8_lossy(data), None),
+			ClipboardKind::UriList => set.file_list(&uri_list_to_paths(&String::from_utf8_lossy(data))?),
+		};
+
+		res.map_err(|e| format!("Could not set {} to clipboard. Cause: {e}", kind.as_str()))?;
+
+		Ok(())
+	}
+}
+
+// region:    --- Support
+
+/// Note: arboard returns images in RGBA8 format.
+fn encode_png(image: &ImageData) -> Result<Vec<u8>> {
+	let mut png_bytes = Vec::new();
+	image::codecs::png::PngEncoder::new(&mut png_bytes)
+		.write_image(
+			&image.bytes,
+			image.width as u32,
+			image.height as u32,
+			image::ExtendedColorType::Rgba8,
+		)
+		.map_err(|e| format!("Failed to encode clipboard image to PNG. Cause: {e}"))?;
+
+	Ok(png_bytes)
+}
+
+// endregion: --- Support
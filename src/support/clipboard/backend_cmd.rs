@@ -0,0 +1,74 @@
+use crate::Result;
+use crate::support::clipboard::{ClipboardBackend, ClipboardKind};
+use crate::support::proc;
+
+/// The clipboard command line tools (for Wayland-only or headless setups where arboard fails).
+#[derive(Debug, Clone, Copy)]
+pub enum ClipboardTool {
+	/// `wl-paste` / `wl-copy` (Wayland)
+	WlClipboard,
+	/// `xclip` (X11)
+	Xclip,
+}
+
+/// The system clipboard through a command line tool.
+pub struct CommandBackend {
+	tool: ClipboardTool,
+}
+
+impl CommandBackend {
+	pub fn new(tool: ClipboardTool) -> Self {
+		Self { tool }
+	}
+
+	/// The tool commands are all in the PATH.
+	pub fn is_available(tool: ClipboardTool) -> bool {
+		match tool {
+			ClipboardTool::WlClipboard => proc::command_exists("wl-paste") && proc::command_exists("wl-copy"),
+			ClipboardTool::Xclip => proc::command_exists("xclip"),
+		}
+	}
+}
+
+impl ClipboardBackend for CommandBackend {
+	fn name(&self) -> &'static str {
+		match self.tool {
+			ClipboardTool::WlClipboard => "wl-clipboard",
+			ClipboardTool::Xclip => "xclip",
+		}
+	}
+
+	fn read(&mut self, kind: ClipboardKind) -> Result<Vec<u8>> {
+		let target = target(self.tool, kind);
+		let res = match self.tool {
+			ClipboardTool::WlClipboard => proc::run_proc_bytes("wl-paste", &["--no-newline", "--type", target]),
+			ClipboardTool::Xclip => proc::run_proc_bytes("xclip", &["-selection", "clipboard", "-t", target, "-o"]),
+		};
+
+		res.map_err(|e| format!("Could not get {} from clipboard. Cause: {e}", kind.as_str()).into())
+	}
+
+	fn write(&mut self, kind: ClipboardKind, data: &[u8]) -> Result<()> {
+		let target = target(self.tool, kind);
+		let res = match self.tool {
+			ClipboardTool::WlClipboard => proc::run_proc_with_stdin("wl-copy", &["--type", target], data),
+			ClipboardTool::Xclip => {
+				proc::run_proc_with_stdin("xclip", &["-selection", "clipboard", "-t", target, "-i"], data)
+			}
+		};
+
+		res.map_err(|e| format!("Could not set {} to clipboard. Cause: {e}", kind.as_str()).into())
+	}
+}
+
+// region:    --- Support
+
+/// The mime type, or the X11 target for the xclip text (`UTF8_STRING`).
+fn target(tool: ClipboardTool, kind: ClipboardKind) -> &'static str {
+	match (tool, kind) {
+		(ClipboardTool::Xclip, ClipboardKind::Text) => "UTF8_STRING",
+		(_, kind) => kind.mime(),
+	}
+}
+
+// endregion: --- Support
@@ -0,0 +1,56 @@
+use crate::Result;
+use crate::support::clipboard::{ClipboardBackend, ClipboardKind};
+use crate::support::files;
+use simple_fs::SPath;
+use std::fs;
+
+/// A fake clipboard in a directory, one file per kind (e.g., `image.png`, `text.txt`), for the tests and CI.
+pub struct FileBackend {
+	dir: SPath,
+}
+
+impl FileBackend {
+	pub fn new(dir: SPath) -> Self {
+		Self { dir }
+	}
+
+	fn kind_path(&self, kind: ClipboardKind) -> SPath {
+		let file_name = match kind {
+			ClipboardKind::Image => "image.png",
+			ClipboardKind::Text => "text.txt",
+			ClipboardKind::Html => "html.html",
+			ClipboardKind::UriList => "uri-list.txt",
+		};
+		self.dir.join(file_name)
+	}
+}
+
+impl ClipboardBackend for FileBackend {
+	fn name(&self) -> &'static str {
+		"file"
+	}
+
+	fn read(&mut self, kind: ClipboardKind) -> Result<Vec<u8>> {
+		let path = self.kind_path(kind);
+		if !path.exists() {
+			return Err(format!("No {} in the file clipboard ({path} not found)", kind.as_str()).into());
+		}
+		Ok(fs::read(path.std_path())?)
+	}
+
+	/// Note: Like a system clipboard, a write replaces the content of every kind.
+	fn write(&mut self, kind: ClipboardKind, data: &[u8]) -> Result<()> {
+		for other in [
+			ClipboardKind::Image,
+			ClipboardKind::Text,
+			ClipboardKind::Html,
+			ClipboardKind::UriList,
+		] {
+			let other_path = self.kind_path(other);
+			if other != kind && other_path.exists() {
+				fs::remove_file(other_path.std_path())?;
+			}
+		}
+		files::write_atomic(&self.kind_path(kind), data)
+	}
+}
@@ -0,0 +1,15 @@
+// region:    --- Modules
+
+mod backend;
+mod backend_arboard;
+mod backend_cmd;
+mod backend_file;
+mod types;
+
+pub use backend::*;
+pub use backend_arboard::*;
+pub use backend_cmd::*;
+pub use backend_file::*;
+pub use types::*;
+
+// endregion: --- Modules
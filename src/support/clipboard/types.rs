@@ -0,0 +1,90 @@
+use crate::Result;
+use crate::support::text;
+use std::path::PathBuf;
+
+// region:    --- Types
+
+/// The clipboard content kinds (images are always exchanged as PNG bytes, the others as UTF-8).
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum ClipboardKind {
+	Image,
+	Text,
+	Html,
+	UriList,
+}
+
+impl ClipboardKind {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			ClipboardKind::Image => "image",
+			ClipboardKind::Text => "text",
+			ClipboardKind::Html => "html",
+			ClipboardKind::UriList => "uri-list",
+		}
+	}
+
+	pub fn mime(&self) -> &'static str {
+		match self {
+			ClipboardKind::Image => "image/png",
+			ClipboardKind::Text => "text/plain",
+			ClipboardKind::Html => "text/html",
+			ClipboardKind::UriList => "text/uri-list",
+		}
+	}
+}
+
+/// A system (or fake) clipboard.
+pub trait ClipboardBackend {
+	/// Backend name for the messages (e.g., "arboard", "wl-clipboard")
+	fn name(&self) -> &'static str;
+
+	/// Returns the clipboard content of this kind (PNG bytes for an image, UTF-8 for the others).
+	fn read(&mut self, kind: ClipboardKind) -> Result<Vec<u8>>;
+
+	/// Replaces the clipboard content with this content (PNG bytes for an image, UTF-8 for the others).
+	fn write(&mut self, kind: ClipboardKind, data: &[u8]) -> Result<()>;
+}
+
+// endregion: --- Types
+
+/// Build a `text/uri-list` content (one `file://` URI per line, CRLF separated) from paths.
+pub fn paths_to_uri_list(paths: &[PathBuf]) -> String {
+	paths
+		.iter()
+		.map(|path| format!("file://{}", percent_encode_path(&path.to_string_lossy())))
+		.collect::<Vec<_>>()
+		.join("\r\n")
+}
+
+/// Returns the paths of the `file://` URIs of a `text/uri-list` content (comment lines skipped).
+/// Fails on other URI schemes.
+pub fn uri_list_to_paths(uri_list: &str) -> Result<Vec<PathBuf>> {
+	let mut paths = Vec::new();
+	for line in uri_list
+		.lines()
+		.map(|l| l.trim())
+		.filter(|l| !l.is_empty() && !l.starts_with('#'))
+	{
+		let Some(path) = line.strip_prefix("file://") else {
+			return Err(format!("Only file:// URIs are supported in the uri-list (got '{line}')").into());
+		};
+		paths.push(PathBuf::from(text::percent_decode(path)));
+	}
+	Ok(paths)
+}
+
+// region:    --- Support
+
+fn percent_encode_path(path: &str) -> String {
+	let mut encoded = String::new();
+	for byte in path.bytes() {
+		if byte.is_ascii_alphanumeric() || b"/-._~".contains(&byte) {
+			encoded.push(byte as char);
+		} else {
+			encoded.push_str(&format!("%{byte:02X}"));
+		}
+	}
+	encoded
+}
+
+// endregion: --- Support
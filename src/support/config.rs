@@ -0,0 +1,142 @@
+use crate::Result;
+use crate::support::jsons;
+use serde::Deserialize;
+use simple_fs::{SPath, home_dir};
+use std::collections::HashMap;
+
+// region:    --- Types
+
+/// The jc-zed-tasks config (`~/.config/jc-zed-tasks/config.json`, jsonc).
+/// All sections are optional.
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct AppConfig {
+	/// Zed keymap bindings for our tasks (used by `zed-keymap install`)
+	pub keymap: Vec<KeymapBindingConfig>,
+
+	/// Which Zed tasks.json files to touch after a command (Zed env refresh workaround)
+	pub touch_tasks_json: TouchTasksJsonConfig,
+
+	/// Zed themes to cycle through (used by `zed-theme`)
+	pub zed_theme: ZedThemeConfig,
+
+	/// Backup/journal retention of the config file writes (used by `history` and `undo`)
+	pub history: HistoryConfig,
+
+	/// Clipboard backend (used by `save-clipboard-image` and `clipboard`)
+	pub clipboard: ClipboardConfig,
+
+	/// Image naming defaults (used by `save-clipboard-image`)
+	pub save_clipboard_image: SaveClipboardImageConfig,
+}
+
+#[derive(Debug, Clone, Deserialize)]
+pub struct KeymapBindingConfig {
+	/// Zed keymap context (e.g., "Workspace", "Editor"). None for the global (no context) block.
+	pub context: Option<String>,
+	/// The key chord (e.g., "cmd-alt-h")
+	pub key: String,
+	/// The task name from the catalog (e.g., "md-to-html")
+	pub task: String,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct TouchTasksJsonConfig {
+	/// Touch `~/.config/zed/tasks.json` (default true)
+	pub global: bool,
+	/// Touch `<ZED_WORKTREE_ROOT>/.zed/tasks.json` (default false)
+	pub project: bool,
+}
+
+impl Default for TouchTasksJsonConfig {
+	fn default() -> Self {
+		Self {
+			global: true,
+			project: false,
+		}
+	}
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ZedThemeConfig {
+	/// The theme names to cycle through with `zed-theme next|prev`
+	pub list: Vec<String>,
+	/// Default light theme when converting a plain theme to `{mode, light, dark}`
+	pub light: Option<String>,
+	/// Default dark theme when converting a plain theme to `{mode, light, dark}`
+	pub dark: Option<String>,
+	/// Zed theme name -> Alacritty colors import path (as written in alacritty.toml `import`)
+	pub alacritty_colors: HashMap<String, String>,
+}
+
+#[derive(Debug, Deserialize)]
+#[serde(default)]
+pub struct HistoryConfig {
+	/// Max number of journal entries (and their backups) kept (default 100)
+	pub max_entries: usize,
+}
+
+impl Default for HistoryConfig {
+	fn default() -> Self {
+		Self { max_entries: 100 }
+	}
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct ClipboardConfig {
+	/// `auto` (default), `arboard`, `wl-clipboard`, `xclip`, or `file:<dir>` (overridden by `JC_ZED_TASKS_CLIPBOARD`)
+	pub backend: Option<String>,
+}
+
+#[derive(Debug, Default, Deserialize)]
+#[serde(default)]
+pub struct SaveClipboardImageConfig {
+	/// Default name template (e.g., "image-{n:02}", see `--name`)
+	pub name: Option<String>,
+	/// Per-directory defaults (the deepest matching `dir` wins)
+	pub dirs: Vec<SaveClipboardImageDirConfig>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct SaveClipboardImageDirConfig {
+	/// The directory (and its sub directories), absolute or `~/...`
+	pub dir: String,
+	/// Name template for the images of this directory (e.g., "{date}-post-{n:02}")
+	pub name: Option<String>,
+}
+
+// endregion: --- Types
+
+/// Returns the jc-zed-tasks config directory (`~/.config/jc-zed-tasks`).
+pub fn config_dir() -> Result<SPath> {
+	Ok(home_dir()?.join(".config/jc-zed-tasks"))
+}
+
+/// Returns the jc-zed-tasks state directory (`$XDG_STATE_HOME/jc-zed-tasks`, default `~/.local/state/jc-zed-tasks`).
+pub fn state_dir() -> Result<SPath> {
+	let state_home = match std::env::var("XDG_STATE_HOME").ok().filter(|v| !v.is_empty()) {
+		Some(state_home) => SPath::new(state_home),
+		None => home_dir()?.join(".local/state"),
+	};
+	Ok(state_home.join("jc-zed-tasks"))
+}
+
+/// Load the `config.json`, or the default config if the file does not exist.
+pub fn load_config() -> Result<AppConfig> {
+	let config_path = config_dir()?.join("config.json");
+	if !config_path.exists() {
+		return Ok(AppConfig::default());
+	}
+
+	let Some(value) = jsons::load_jsons_to_serde_value(&config_path)? else {
+		return Ok(AppConfig::default());
+	};
+
+	let config: AppConfig = serde_json::from_value(value)
+		.map_err(|err| crate::Error::custom(format!("Invalid config file {config_path}. Cause: {err}")))?;
+
+	Ok(config)
+}
@@ -0,0 +1,148 @@
+const CONTEXT_LINES: usize = 3;
+
+enum DiffLine<'a> {
+	Same(&'a str),
+	Del(&'a str),
+	Add(&'a str),
+}
+
+/// Returns a unified diff (`---`/`+++`/`@@` format) between `old` and `new`.
+/// Returns an empty string when the contents have the same lines.
+pub fn unified_diff(old: &str, new: &str, old_label: &str, new_label: &str) -> String {
+	let old_lines: Vec<&str> = old.lines().collect();
+	let new_lines: Vec<&str> = new.lines().collect();
+
+	let diff_lines = diff_lines(&old_lines, &new_lines);
+	if !diff_lines.iter().any(|l| !matches!(l, DiffLine::Same(_))) {
+		return String::new();
+	}
+
+	let mut out = format!("--- {old_label}\n+++ {new_label}\n");
+
+	// -- Compute the (old_line_no, new_line_no) of each diff line (0 based)
+	let mut positions = Vec::with_capacity(diff_lines.len());
+	let (mut old_no, mut new_no) = (0, 0);
+	for line in diff_lines.iter() {
+		positions.push((old_no, new_no));
+		match line {
+			DiffLine::Same(_) => {
+				old_no += 1;
+				new_no += 1;
+			}
+			DiffLine::Del(_) => old_no += 1,
+			DiffLine::Add(_) => new_no += 1,
+		}
+	}
+
+	// -- Group changes into hunks
+	let change_idxs: Vec<usize> = diff_lines
+		.iter()
+		.enumerate()
+		.filter(|(_, l)| !matches!(l, DiffLine::Same(_)))
+		.map(|(i, _)| i)
+		.collect();
+
+	let mut hunks: Vec<(usize, usize)> = Vec::new();
+	for idx in change_idxs {
+		let start = idx.saturating_sub(CONTEXT_LINES);
+		let end = (idx + CONTEXT_LINES + 1).min(diff_lines.len());
+		match hunks.last_mut() {
+			Some(last) if start <= last.1 => last.1 = end,
+			_ => hunks.push((start, end)),
+		}
+	}
+
+	for (start, end) in hunks {
+		let (old_start, new_start) = positions[start];
+		let lines = &diff_lines[start..end];
+		let old_count = lines.iter().filter(|l| !matches!(l, DiffLine::Add(_))).count();
+		let new_count = lines.iter().filter(|l| !matches!(l, DiffLine::Del(_))).count();
+
+		out.push_str(&format!(
+			"@@ -{},{old_count} +{},{new_count} @@\n",
+			hunk_start(old_start, old_count),
+			hunk_start(new_start, new_count)
+		));
+
+		for line in lines {
+			match line {
+				DiffLine::Same(l) => out.push_str(&format!(" {l}\n")),
+				DiffLine::Del(l) => out.push_str(&format!("-{l}\n")),
+				DiffLine::Add(l) => out.push_str(&format!("+{l}\n")),
+			}
+		}
+	}
+
+	out
+}
+
+/// Colorize a unified diff with ANSI colors (red deletions, green additions, cyan hunk headers).
+pub fn colorize_unified_diff(diff: &str) -> String {
+	let mut out = String::with_capacity(diff.len());
+	for line in diff.lines() {
+		let color = if line.starts_with("---") || line.starts_with("+++") {
+			"\x1b[1m"
+		} else if line.starts_with("@@") {
+			"\x1b[36m"
+		} else if line.starts_with('-') {
+			"\x1b[31m"
+		} else if line.starts_with('+') {
+			"\x1b[32m"
+		} else {
+			""
+		};
+		if color.is_empty() {
+			out.push_str(line);
+		} else {
+			out.push_str(&format!("{color}{line}\x1b[0m"));
+		}
+		out.push('\n');
+	}
+	out
+}
+
+// region:    --- Support
+
+/// Unified diff line numbers are 1 based, except for empty ranges.
+fn hunk_start(start: usize, count: usize) -> usize {
+	if count == 0 { start } else { start + 1 }
+}
+
+/// Simple LCS based line diff (fine for config file sizes).
+fn diff_lines<'a>(old: &[&'a str], new: &[&'a str]) -> Vec<DiffLine<'a>> {
+	let (n, m) = (old.len(), new.len());
+
+	// lcs[i][j] = LCS length of old[i..] and new[j..]
+	let mut lcs = vec![vec![0usize; m + 1]; n + 1];
+	for i in (0..n).rev() {
+		for j in (0..m).rev() {
+			lcs[i][j] = if old[i] == new[j] {
+				lcs[i + 1][j + 1] + 1
+			} else {
+				lcs[i + 1][j].max(lcs[i][j + 1])
+			};
+		}
+	}
+
+	let mut res = Vec::with_capacity(n.max(m));
+	let (mut i, mut j) = (0, 0);
+	while i < n && j < m {
+		if old[i] == new[j] {
+			res.push(DiffLine::Same(old[i]));
+			i += 1;
+			j += 1;
+		} else if lcs[i + 1][j] >= lcs[i][j + 1] {
+			res.push(DiffLine::Del(old[i]));
+			i += 1;
+		} else {
+			res.push(DiffLine::Add(new[j]));
+			j += 1;
+		}
+	}
+	res.extend(old[i..].iter().map(|l| DiffLine::Del(l)));
+	res.extend(new[j..].iter().map(|l| DiffLine::Add(l)));
+
+	res
+}
+
+// endregion: --- Support
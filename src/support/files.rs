@@ -0,0 +1,172 @@
+use crate::Result;
+use crate::support::hash::fnv1a_64_hex;
+use simple_fs::{SPath, home_dir};
+use std::fs::{self, File, OpenOptions};
+use std::io::Write as _;
+use std::path::PathBuf;
+use std::time::SystemTime;
+
+const LOCK_FILE_NAME: &str = ".lock";
+
+// region:    --- Types
+
+/// The modification time and content hash of a file (both None if the file does not exist).
+/// Used to detect that a file changed between a read and a write.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct FileStamp {
+	pub modified: Option<SystemTime>,
+	pub hash: Option<String>,
+}
+
+impl FileStamp {
+	pub fn of(path: &SPath) -> Result<Self> {
+		let (_, stamp) = read_with_stamp(path)?;
+		Ok(stamp)
+	}
+}
+
+/// An exclusive advisory lock on a directory (released on drop).
+pub struct DirLock {
+	_file: File,
+}
+
+// endregion: --- Types
+
+/// Take the exclusive advisory lock of a directory (`<dir>/.lock`), waiting if another process has it.
+pub fn lock_dir(dir: &SPath) -> Result<DirLock> {
+	fs::create_dir_all(dir.std_path())?;
+	let lock_path = dir.join(LOCK_FILE_NAME);
+	let file = OpenOptions::new()
+		.create(true)
+		.truncate(false)
+		.write(true)
+		.open(lock_path.std_path())?;
+	file.lock()
+		.map_err(|err| crate::Error::custom(format!("Cannot lock {lock_path}. Cause: {err}")))?;
+
+	Ok(DirLock { _file: file })
+}
+
+/// Read the file content (None if it does not exist) with its stamp.
+pub fn read_with_stamp(path: &SPath) -> Result<(Option<String>, FileStamp)> {
+	if !path.exists() {
+		return Ok((
+			None,
+			FileStamp {
+				modified: None,
+				hash: None,
+			},
+		));
+	}
+
+	let modified = fs::metadata(path.std_path())?.modified().ok();
+	let content = fs::read_to_string(path.std_path())?;
+	let hash = Some(fnv1a_64_hex(content.as_bytes()));
+
+	Ok((Some(content), FileStamp { modified, hash }))
+}
+
+/// Write the file atomically (temp file in the same dir, then rename).
+/// - The permissions of the existing file are kept.
+/// - A symlinked file (e.g., from a dotfiles repo) is written at its target, keeping the symlink.
+pub fn write_atomic(path: &SPath, content: &[u8]) -> Result<()> {
+	let target = resolve_symlink(path)?;
+	let dir = match target.parent() {
+		Some(dir) if !dir.as_os_str().is_empty() => dir.to_path_buf(),
+		_ => PathBuf::from("."),
+	};
+	fs::create_dir_all(&dir)?;
+
+	let file_name = target
+		.file_name()
+		.map(|n| n.to_string_lossy().to_string())
+		.unwrap_or_default();
+	let tmp_path = dir.join(format!(".{file_name}.{}.tmp", std::process::id()));
+
+	let res = (|| -> Result<()> {
+		let mut tmp_file = File::create(&tmp_path)?;
+		tmp_file.write_all(content)?;
+		tmp_file.sync_all()?;
+		if let Ok(metadata) = fs::metadata(&target) {
+			fs::set_permissions(&tmp_path, metadata.permissions())?;
+		}
+		fs::rename(&tmp_path, &target)?;
+		Ok(())
+	})();
+
+	if res.is_err() {
+		let _ = fs::remove_file(&tmp_path);
+	}
+
+	res
+}
+
+/// Expand a leading `~/` to the home directory (e.g., for the paths of the config).
+pub fn expand_home(path: &str) -> Result<SPath> {
+	match path.strip_prefix("~/") {
+		Some(rest) => Ok(home_dir()?.join(rest)),
+		None => Ok(SPath::new(path)),
+	}
+}
+
+// region:    --- Support
+
+/// Follow the symlinks of the path (the path itself when not a symlink or missing).
+fn resolve_symlink(path: &SPath) -> Result<PathBuf> {
+	let path = path.std_path();
+	let is_symlink = fs::symlink_metadata(path)
+		.map(|m| m.file_type().is_symlink())
+		.unwrap_or(false);
+	if is_symlink && path.exists() {
+		Ok(fs::canonicalize(path)?)
+	} else {
+		Ok(path.to_path_buf())
+	}
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+	use std::os::unix::fs::PermissionsExt as _;
+
+	#[test]
+	fn test_support_files_write_atomic_keep_permissions_and_symlink() -> Result<()> {
+		// -- Setup & Fixtures
+		let base_dir = std::env::temp_dir().join(format!("jc-zed-tasks-files-{}", std::process::id()));
+		let base_dir = SPath::from_std_path(&base_dir)?;
+		fs::create_dir_all(base_dir.std_path())?;
+		let target = base_dir.join("settings.json");
+		let link = base_dir.join("settings-link.json");
+		fs::write(target.std_path(), "v1")?;
+		fs::set_permissions(target.std_path(), fs::Permissions::from_mode(0o600))?;
+		std::os::unix::fs::symlink(target.std_path(), link.std_path())?;
+		let stamp_before = FileStamp::of(&target)?;
+
+		// -- Exec
+		write_atomic(&link, b"v2")?;
+
+		// -- Check
+		assert!(fs::symlink_metadata(link.std_path())?.file_type().is_symlink());
+		assert_eq!(fs::read_to_string(target.std_path())?, "v2");
+		assert_eq!(fs::metadata(target.std_path())?.permissions().mode() & 0o777, 0o600);
+		assert_ne!(FileStamp::of(&target)?, stamp_before);
+		let tmp_files = fs::read_dir(base_dir.std_path())?
+			.filter_map(|e| e.ok())
+			.filter(|e| e.file_name().to_string_lossy().ends_with(".tmp"))
+			.count();
+		assert_eq!(tmp_files, 0);
+
+		// -- Cleanup
+		fs::remove_dir_all(base_dir.std_path())?;
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,14 @@
+/// FNV-1a 64 bit hash as a 16 chars hex string.
+/// Note: Not cryptographic, but stable across builds (used for change detection).
+pub fn fnv1a_64_hex(bytes: &[u8]) -> String {
+	const OFFSET_BASIS: u64 = 0xcbf29ce484222325;
+	const PRIME: u64 = 0x100000001b3;
+
+	let mut hash = OFFSET_BASIS;
+	for b in bytes {
+		hash ^= *b as u64;
+		hash = hash.wrapping_mul(PRIME);
+	}
+
+	format!("{hash:016x}")
+}
@@ -0,0 +1,420 @@
+use crate::Result;
+use crate::support::hash::fnv1a_64_hex;
+use crate::support::{config, files, writer};
+use serde::{Deserialize, Serialize};
+use simple_fs::SPath;
+use std::fs::{self, OpenOptions};
+use std::io::Write as _;
+
+const JOURNAL_FILE_NAME: &str = "journal.jsonl";
+const UNDO_COMMAND_PREFIX: &str = "undo";
+const EDIT_MAX_ATTEMPTS: usize = 5;
+
+// region:    --- Types
+
+/// One config file write (the before/after contents are stored in the backups dir).
+#[derive(Debug, Clone, Serialize, Deserialize)]
+pub struct HistoryEntry {
+	pub id: u64,
+	pub time: String,
+	pub command: String,
+	pub file: String,
+	/// None when the file did not exist before
+	pub before_hash: Option<String>,
+	/// None when the write removed the file (undo of a file creation)
+	pub after_hash: Option<String>,
+	#[serde(default)]
+	pub undone: bool,
+}
+
+/// The journal and backups of the config file writes (in `<state_dir>/history`).
+pub struct History {
+	dir: SPath,
+	max_entries: usize,
+}
+
+// endregion: --- Types
+
+/// Write a config file (e.g., Zed settings.json, alacritty.toml) with a backup and journal entry,
+/// so that it can be listed/diffed with `history` and restored with `undo`.
+pub fn write_config_file(command: &str, path: &SPath, content: &str) -> Result<()> {
+	History::open()?.write_file(command, path, content)
+}
+
+/// Edit a config file (read-modify-write) with a backup and journal entry.
+/// The `edit` fn gets the current content (None if the file does not exist), and is called again
+/// if the file changed between the read and the write (e.g., saved by Zed meanwhile).
+pub fn edit_config_file(command: &str, path: &SPath, edit: impl FnMut(Option<&str>) -> Result<String>) -> Result<()> {
+	History::open()?.edit_file(command, path, edit)
+}
+
+impl History {
+	/// Open the default history (state dir, and retention from config).
+	pub fn open() -> Result<Self> {
+		let max_entries = config::load_config()?.history.max_entries;
+		Ok(Self::new(config::state_dir()?.join("history"), max_entries))
+	}
+
+	pub fn new(dir: SPath, max_entries: usize) -> Self {
+		Self { dir, max_entries }
+	}
+
+	/// Write the content to the file, and record the before/after in the history.
+	/// Does nothing if the file already has this content.
+	pub fn write_file(&self, command: &str, path: &SPath, content: &str) -> Result<()> {
+		let _lock = files::lock_dir(&self.dir)?;
+		self.write_file_locked(command, path, Some(content))
+	}
+
+	/// Edit the file (read-modify-write), and record the before/after in the history.
+	/// If the file changed between the read and the write, the edit is retried from the new content.
+	pub fn edit_file(
+		&self,
+		command: &str,
+		path: &SPath,
+		mut edit: impl FnMut(Option<&str>) -> Result<String>,
+	) -> Result<()> {
+		let _lock = files::lock_dir(&self.dir)?;
+
+		for _ in 0..EDIT_MAX_ATTEMPTS {
+			let (content, stamp) = files::read_with_stamp(path)?;
+			let new_content = edit(content.as_deref())?;
+			if files::FileStamp::of(path)? == stamp {
+				return self.write_file_locked(command, path, Some(&new_content));
+			}
+			std::thread::sleep(std::time::Duration::from_millis(50));
+		}
+
+		Err(
+			format!("File {path} kept changing while editing it (not written after {EDIT_MAX_ATTEMPTS} attempts)")
+				.into(),
+		)
+	}
+
+	/// The journal entries (oldest first).
+	pub fn list(&self) -> Result<Vec<HistoryEntry>> {
+		let journal_path = self.journal_path();
+		if !journal_path.exists() {
+			return Ok(Vec::new());
+		}
+
+		let content = fs::read_to_string(journal_path.std_path())?;
+		let mut entries = Vec::new();
+		for line in content.lines().filter(|l| !l.trim().is_empty()) {
+			let entry: HistoryEntry = serde_json::from_str(line).map_err(|err| {
+				crate::Error::custom(format!("Invalid history journal line in {journal_path}. Cause: {err}"))
+			})?;
+			entries.push(entry);
+		}
+
+		Ok(entries)
+	}
+
+	pub fn get(&self, id: u64) -> Result<HistoryEntry> {
+		self.list()?
+			.into_iter()
+			.find(|e| e.id == id)
+			.ok_or_else(|| format!("No history entry #{id}").into())
+	}
+
+	/// The file content before the write (None if the file did not exist).
+	pub fn before_content(&self, entry: &HistoryEntry) -> Result<Option<String>> {
+		if entry.before_hash.is_none() {
+			return Ok(None);
+		}
+		let content = fs::read_to_string(self.backup_path(entry.id, "before").std_path())?;
+		Ok(Some(content))
+	}
+
+	/// The file content after the write (None if the write removed the file).
+	pub fn after_content(&self, entry: &HistoryEntry) -> Result<Option<String>> {
+		if entry.after_hash.is_none() {
+			return Ok(None);
+		}
+		let content = fs::read_to_string(self.backup_path(entry.id, "after").std_path())?;
+		Ok(Some(content))
+	}
+
+	/// Restore the file of an entry (default the last one not undone) to its before content.
+	/// Fails if the file changed since this entry.
+	pub fn undo(&self, id: Option<u64>) -> Result<HistoryEntry> {
+		let _lock = files::lock_dir(&self.dir)?;
+		let mut entries = self.list()?;
+
+		let entry = match id {
+			Some(id) => entries.iter().find(|e| e.id == id),
+			None => entries
+				.iter()
+				.rev()
+				.find(|e| !e.undone && !e.command.starts_with(UNDO_COMMAND_PREFIX)),
+		}
+		.cloned()
+		.ok_or_else(|| match id {
+			Some(id) => format!("No history entry #{id}"),
+			None => "No history entry to undo".to_string(),
+		})?;
+
+		if entry.undone {
+			return Err(format!("History entry #{} already undone", entry.id).into());
+		}
+
+		// -- Check the file did not change since
+		let path = SPath::new(&entry.file);
+		let current_hash = if path.exists() {
+			Some(fnv1a_64_hex(fs::read(path.std_path())?.as_slice()))
+		} else {
+			None
+		};
+		if current_hash != entry.after_hash {
+			return Err(format!(
+				"File {} changed since history entry #{} (not restored)",
+				entry.file, entry.id
+			)
+			.into());
+		}
+
+		// -- Restore (removes the file if the entry created it)
+		let before = self.before_content(&entry)?;
+		self.write_file_locked(
+			&format!("{UNDO_COMMAND_PREFIX} #{}", entry.id),
+			&path,
+			before.as_deref(),
+		)?;
+		if writer::is_dry_run() {
+			return Ok(entry);
+		}
+
+		// -- Mark as undone (reload, since the restore write added an entry)
+		entries = self.list()?;
+		for e in entries.iter_mut().filter(|e| e.id == entry.id) {
+			e.undone = true;
+		}
+		self.save_entries(&entries)?;
+
+		Ok(entry)
+	}
+}
+
+// region:    --- Support
+
+impl History {
+	/// Same as `write_file`, with the history dir lock already taken.
+	/// A None content removes the file (recorded with no after content).
+	fn write_file_locked(&self, command: &str, path: &SPath, content: Option<&str>) -> Result<()> {
+		let before = if path.exists() {
+			Some(fs::read_to_string(path.std_path())?)
+		} else {
+			None
+		};
+		if before.as_deref() == content {
+			return Ok(());
+		}
+		if writer::is_dry_run() {
+			return match content {
+				Some(content) => writer::print_write_preview(path, content.as_bytes()),
+				None => writer::remove_file(path),
+			};
+		}
+
+		let mut entries = self.list()?;
+		let id = entries.last().map(|e| e.id + 1).unwrap_or(1);
+		let file = std::path::absolute(path.std_path())?;
+		let file = file.to_string_lossy().to_string();
+
+		// -- Backup
+		fs::create_dir_all(self.backups_dir().std_path())?;
+		if let Some(before) = before.as_deref() {
+			fs::write(self.backup_path(id, "before").std_path(), before)?;
+		}
+		if let Some(content) = content {
+			fs::write(self.backup_path(id, "after").std_path(), content)?;
+		}
+
+		// -- Write
+		match content {
+			Some(content) => files::write_atomic(path, content.as_bytes())?,
+			None => fs::remove_file(path.std_path())?,
+		}
+
+		// -- Journal
+		let entry = HistoryEntry {
+			id,
+			time: chrono::Local::now().format("%Y-%m-%d %H:%M:%S").to_string(),
+			command: command.to_string(),
+			file,
+			before_hash: before.as_deref().map(|b| fnv1a_64_hex(b.as_bytes())),
+			after_hash: content.map(|c| fnv1a_64_hex(c.as_bytes())),
+			undone: false,
+		};
+		self.append_entry(&entry)?;
+		entries.push(entry);
+
+		if entries.len() > self.max_entries {
+			self.prune(entries)?;
+		}
+
+		Ok(())
+	}
+
+	fn journal_path(&self) -> SPath {
+		self.dir.join(JOURNAL_FILE_NAME)
+	}
+
+	fn backups_dir(&self) -> SPath {
+		self.dir.join("backups")
+	}
+
+	fn backup_path(&self, id: u64, kind: &str) -> SPath {
+		self.backups_dir().join(format!("{id:05}.{kind}"))
+	}
+
+	fn append_entry(&self, entry: &HistoryEntry) -> Result<()> {
+		fs::create_dir_all(self.dir.std_path())?;
+		let mut file = OpenOptions::new()
+			.create(true)
+			.append(true)
+			.open(self.journal_path().std_path())?;
+		writeln!(file, "{}", serde_json::to_string(entry)?)?;
+		Ok(())
+	}
+
+	fn save_entries(&self, entries: &[HistoryEntry]) -> Result<()> {
+		let mut content = String::new();
+		for entry in entries {
+			content.push_str(&serde_json::to_string(entry)?);
+			content.push('\n');
+		}
+		files::write_atomic(&self.journal_path(), content.as_bytes())?;
+		Ok(())
+	}
+
+	/// Keep the last `max_entries` entries, and remove the backups of the others.
+	fn prune(&self, entries: Vec<HistoryEntry>) -> Result<()> {
+		let remove_count = entries.len().saturating_sub(self.max_entries);
+		let (removed, kept) = entries.split_at(remove_count);
+
+		for entry in removed {
+			for kind in ["before", "after"] {
+				let backup_path = self.backup_path(entry.id, kind);
+				if backup_path.exists() {
+					fs::remove_file(backup_path.std_path())?;
+				}
+			}
+		}
+
+		self.save_entries(kept)
+	}
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_support_history_write_undo_and_prune() -> Result<()> {
+		// -- Setup & Fixtures
+		let base_dir = std::env::temp_dir().join(format!("jc-zed-tasks-history-{}", std::process::id()));
+		let base_dir = SPath::from_std_path(&base_dir)?;
+		let history = History::new(base_dir.join("history"), 3);
+		let file = base_dir.join("settings.json");
+
+		// -- Exec
+		history.write_file("test", &file, "v1")?;
+		history.write_file("test", &file, "v2")?;
+		history.write_file("test", &file, "v2")?; // no change, no entry
+		let undone = history.undo(None)?;
+
+		// -- Check
+		assert_eq!(undone.id, 2);
+		assert_eq!(fs::read_to_string(file.std_path())?, "v1");
+		let entries = history.list()?;
+		assert_eq!(entries.len(), 3);
+		assert!(entries[1].undone);
+		assert_eq!(entries[2].command, "undo #2");
+		// file changed outside of the history, so cannot be undone
+		fs::write(file.std_path(), "external")?;
+		assert!(history.undo(Some(1)).is_err());
+
+		// -- Check prune
+		history.write_file("test", &file, "v3")?;
+		let entries = history.list()?;
+		assert_eq!(entries.len(), 3);
+		assert_eq!(entries[0].id, 2);
+		assert!(!history.backup_path(1, "after").exists());
+
+		// -- Cleanup
+		fs::remove_dir_all(base_dir.std_path())?;
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_support_history_undo_created_file() -> Result<()> {
+		// -- Setup & Fixtures
+		let base_dir = std::env::temp_dir().join(format!("jc-zed-tasks-history-created-{}", std::process::id()));
+		let base_dir = SPath::from_std_path(&base_dir)?;
+		let history = History::new(base_dir.join("history"), 10);
+		let file = base_dir.join("tasks.json");
+
+		// -- Exec
+		history.write_file("test", &file, "v1")?;
+		history.undo(None)?;
+
+		// -- Check
+		assert!(!file.exists());
+		let entries = history.list()?;
+		assert_eq!(entries.len(), 2);
+		assert!(entries[0].undone);
+		assert_eq!(entries[1].command, "undo #1");
+		assert_eq!(entries[1].after_hash, None);
+		// the undo itself can be undone (recreates the file)
+		history.undo(Some(2))?;
+		assert_eq!(fs::read_to_string(file.std_path())?, "v1");
+		assert_eq!(history.list()?.len(), 3);
+
+		// -- Cleanup
+		fs::remove_dir_all(base_dir.std_path())?;
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_support_history_edit_file_retry_on_change() -> Result<()> {
+		// -- Setup & Fixtures
+		let base_dir = std::env::temp_dir().join(format!("jc-zed-tasks-history-edit-{}", std::process::id()));
+		let base_dir = SPath::from_std_path(&base_dir)?;
+		let history = History::new(base_dir.join("history"), 10);
+		let file = base_dir.join("settings.json");
+		fs::create_dir_all(base_dir.std_path())?;
+		fs::write(file.std_path(), "a")?;
+
+		// -- Exec
+		// first call simulates another writer (e.g., Zed) saving the file between the read and the write
+		let mut calls = 0;
+		history.edit_file("test", &file, |content| {
+			calls += 1;
+			if calls == 1 {
+				fs::write(file.std_path(), "a+zed")?;
+			}
+			Ok(format!("{}+edit", content.unwrap_or_default()))
+		})?;
+
+		// -- Check
+		assert_eq!(calls, 2);
+		assert_eq!(fs::read_to_string(file.std_path())?, "a+zed+edit");
+		assert_eq!(history.list()?.len(), 1);
+
+		// -- Cleanup
+		fs::remove_dir_all(base_dir.std_path())?;
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
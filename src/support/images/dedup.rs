@@ -0,0 +1,234 @@
+use crate::Result;
+use crate::support::hash::fnv1a_64_hex;
+use crate::support::{files, writer};
+use image::RgbaImage;
+use serde::{Deserialize, Serialize};
+use simple_fs::{SPath, list_files};
+use std::collections::BTreeMap;
+use std::fs;
+use std::time::UNIX_EPOCH;
+
+/// The sidecar index file (in the images dir) caching the pixels hash of the images.
+pub const IMAGE_INDEX_FILE_NAME: &str = ".jc-zed-tasks-images.json";
+
+const INDEXED_EXTS: &[&str] = &["png", "jpg", "jpeg", "webp"];
+
+// region:    --- Types
+
+#[derive(Debug, Default, Serialize, Deserialize)]
+struct ImageIndex {
+	#[serde(default)]
+	images: BTreeMap<String, ImageIndexEntry>,
+}
+
+/// The pixels hash of an image file, valid while the file size and modified time are the same.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+struct ImageIndexEntry {
+	hash: String,
+	size: u64,
+	modified: u64,
+}
+
+// endregion: --- Types
+
+/// The hash of the decoded RGBA pixels (with the dimensions), e.g., `800x600-1f0c9a7b3e2d4c5a`.
+pub fn pixels_hash(img: &RgbaImage) -> String {
+	format!("{}x{}-{}", img.width(), img.height(), fnv1a_64_hex(img.as_raw()))
+}
+
+/// The file name of the image in the dir with the same pixels, if any.
+/// The sidecar index is refreshed for the new, changed, and removed files.
+pub fn find_same_image(dir: &SPath, img: &RgbaImage) -> Result<Option<String>> {
+	let mut index = load_index(dir)?;
+	let changed = refresh_index(dir, &mut index)?;
+	if changed {
+		save_index(dir, &index)?;
+	}
+
+	let hash = pixels_hash(img);
+	for (file_name, entry) in index.images.iter() {
+		if entry.hash != hash {
+			continue;
+		}
+		// Note: Lossless files are compared pixel by pixel (the hash is only 64 bits),
+		//       lossy ones can only match by the recorded hash of their source pixels.
+		if is_lossless(file_name) {
+			let Ok(existing) = image::open(dir.join(file_name).std_path()) else {
+				continue;
+			};
+			if existing.to_rgba8() != *img {
+				continue;
+			}
+		}
+		return Ok(Some(file_name.clone()));
+	}
+
+	Ok(None)
+}
+
+/// Records the pixels hash of a saved image (e.g., the source pixels of a JPEG) in the sidecar index.
+pub fn record_image(dir: &SPath, file_name: &str, img: &RgbaImage) -> Result<()> {
+	if writer::is_dry_run() {
+		return Ok(());
+	}
+	let mut index = load_index(dir)?;
+	let (size, modified) = file_stamp(&dir.join(file_name))?;
+	index.images.insert(
+		file_name.to_string(),
+		ImageIndexEntry {
+			hash: pixels_hash(img),
+			size,
+			modified,
+		},
+	);
+	save_index(dir, &index)
+}
+
+// region:    --- Support
+
+fn load_index(dir: &SPath) -> Result<ImageIndex> {
+	let path = dir.join(IMAGE_INDEX_FILE_NAME);
+	if !path.exists() {
+		return Ok(ImageIndex::default());
+	}
+	// Note: A corrupted index is only a cache, so it is rebuilt
+	let index = serde_json::from_str(&fs::read_to_string(path.std_path())?).unwrap_or_default();
+	Ok(index)
+}
+
+/// The sidecar index is a cache, so it is not written in dry-run.
+fn save_index(dir: &SPath, index: &ImageIndex) -> Result<()> {
+	if writer::is_dry_run() || !dir.exists() {
+		return Ok(());
+	}
+	let content = serde_json::to_string_pretty(index)?;
+	files::write_atomic(&dir.join(IMAGE_INDEX_FILE_NAME), content.as_bytes())
+}
+
+/// Returns true if the index changed.
+fn refresh_index(dir: &SPath, index: &mut ImageIndex) -> Result<bool> {
+	if !dir.exists() {
+		return Ok(false);
+	}
+
+	let mut images = BTreeMap::new();
+	for file in list_files(dir, Some(&["*.*"]), None)? {
+		if !INDEXED_EXTS.contains(&file.ext().to_lowercase().as_str()) {
+			continue;
+		}
+		let (size, modified) = file_stamp(&file)?;
+		let entry = match index.images.get(file.name()) {
+			Some(entry) if entry.size == size && entry.modified == modified => entry.clone(),
+			_ => {
+				// Note: Unreadable images are skipped (not indexed)
+				let Ok(img) = image::open(file.std_path()) else {
+					continue;
+				};
+				ImageIndexEntry {
+					hash: pixels_hash(&img.to_rgba8()),
+					size,
+					modified,
+				}
+			}
+		};
+		images.insert(file.name().to_string(), entry);
+	}
+
+	let changed = images != index.images;
+	index.images = images;
+	Ok(changed)
+}
+
+fn file_stamp(path: &SPath) -> Result<(u64, u64)> {
+	let metadata = fs::metadata(path.std_path())?;
+	let modified = metadata
+		.modified()
+		.ok()
+		.and_then(|time| time.duration_since(UNIX_EPOCH).ok())
+		.map(|duration| duration.as_millis() as u64)
+		.unwrap_or_default();
+	Ok((metadata.len(), modified))
+}
+
+/// Note: The WebP images are saved lossless (see `encode_image`).
+fn is_lossless(file_name: &str) -> bool {
+	let file_name = file_name.to_lowercase();
+	file_name.ends_with(".png") || file_name.ends_with(".webp")
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+	use crate::support::images::{ImageFormat, encode_image};
+	use image::DynamicImage;
+
+	#[test]
+	fn test_support_images_dedup_find_same_image() -> Result<()> {
+		// -- Setup & Fixtures
+		let dir = std::env::temp_dir().join(format!("jc-zed-tasks-test-dedup-{}", std::process::id()));
+		let dir = SPath::from_std_path(&dir)?;
+		fs::create_dir_all(dir.std_path())?;
+		let red = RgbaImage::from_pixel(4, 3, image::Rgba([255, 0, 0, 255]));
+		let blue = RgbaImage::from_pixel(4, 3, image::Rgba([0, 0, 255, 255]));
+		red.save(dir.join("image-01.png").std_path())?;
+		// A JPEG only matches by its recorded source hash
+		fs::write(dir.join("image-02.jpg").std_path(), b"not decodable")?;
+		record_image(&dir, "image-02.jpg", &blue)?;
+
+		// -- Exec
+		let same_red = find_same_image(&dir, &red)?;
+		let same_blue = find_same_image(&dir, &blue)?;
+		let other = find_same_image(&dir, &RgbaImage::from_pixel(3, 4, image::Rgba([255, 0, 0, 255])))?;
+		let index = load_index(&dir)?;
+		fs::remove_dir_all(dir.std_path())?;
+
+		// -- Check
+		assert_eq!(same_red.as_deref(), Some("image-01.png"));
+		assert_eq!(same_blue.as_deref(), Some("image-02.jpg"));
+		assert_eq!(other, None);
+		assert_eq!(index.images.len(), 2);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_support_images_dedup_webp_saved_twice() -> Result<()> {
+		// -- Setup & Fixtures
+		let dir = std::env::temp_dir().join(format!("jc-zed-tasks-test-dedup-webp-{}", std::process::id()));
+		let dir = SPath::from_std_path(&dir)?;
+		fs::create_dir_all(dir.std_path())?;
+		let img = RgbaImage::from_fn(5, 4, |x, y| image::Rgba([(x * 50) as u8, (y * 60) as u8, 128, 255]));
+		let save = |file_name: &str| -> Result<String> {
+			if let Some(same) = find_same_image(&dir, &img)? {
+				return Ok(same);
+			}
+			let encoded = encode_image(&DynamicImage::ImageRgba8(img.clone()), ImageFormat::Webp, None, None)?;
+			fs::write(dir.join(file_name).std_path(), &encoded.bytes)?;
+			record_image(&dir, file_name, &img)?;
+			Ok(file_name.to_string())
+		};
+
+		// -- Exec
+		let first = save("image-01.webp")?;
+		// the index is only a cache (rebuilt from the webp pixels)
+		fs::remove_file(dir.join(IMAGE_INDEX_FILE_NAME).std_path())?;
+		let second = save("image-02.webp")?;
+		let index = load_index(&dir)?;
+		fs::remove_dir_all(dir.std_path())?;
+
+		// -- Check
+		assert_eq!(first, "image-01.webp");
+		assert_eq!(second, "image-01.webp");
+		assert_eq!(index.images.len(), 1);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,289 @@
+use crate::Result;
+use image::codecs::jpeg::JpegEncoder;
+use image::codecs::webp::WebPEncoder;
+use image::{DynamicImage, ExtendedColorType, ImageEncoder as _, RgbImage, RgbaImage};
+use std::collections::HashMap;
+
+/// The default JPEG quality (when no `--quality`).
+pub const DEFAULT_JPEG_QUALITY: u8 = 85;
+
+/// The lowest JPEG quality tried to fit in `--max-bytes`.
+const MIN_JPEG_QUALITY: u8 = 20;
+const JPEG_QUALITY_STEP: u8 = 5;
+
+// region:    --- Types
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ImageFormat {
+	/// Lossless, optimized PNG (palette, no alpha when opaque, maximum deflate)
+	#[default]
+	Png,
+	Jpeg,
+	/// Lossless WebP (the image crate has no lossy WebP encoder)
+	Webp,
+}
+
+impl ImageFormat {
+	pub fn ext(&self) -> &'static str {
+		match self {
+			ImageFormat::Png => "png",
+			ImageFormat::Jpeg => "jpg",
+			ImageFormat::Webp => "webp",
+		}
+	}
+}
+
+/// The encoded image bytes, with the quality used (for lossy formats).
+#[derive(Debug)]
+pub struct EncodedImage {
+	pub bytes: Vec<u8>,
+	pub quality: Option<u8>,
+	/// False when the image could not fit in the max bytes (bytes is then the smallest attempt).
+	pub fits: bool,
+}
+
+// endregion: --- Types
+
+/// Encodes the image, stepping the quality down (lossy formats) until it fits in `max_bytes`.
+pub fn encode_image(
+	img: &DynamicImage,
+	format: ImageFormat,
+	quality: Option<u8>,
+	max_bytes: Option<usize>,
+) -> Result<EncodedImage> {
+	match format {
+		ImageFormat::Png => {
+			if quality.is_some() {
+				return Err("--quality does not apply to png (lossless), use --format jpeg".into());
+			}
+			let bytes = optimize_png(&img.to_rgba8())?;
+			let fits = max_bytes.is_none_or(|max| bytes.len() <= max);
+			Ok(EncodedImage {
+				bytes,
+				quality: None,
+				fits,
+			})
+		}
+		ImageFormat::Jpeg => {
+			let quality = quality.unwrap_or(DEFAULT_JPEG_QUALITY);
+			if !(1..=100).contains(&quality) {
+				return Err(format!("Invalid --quality {quality} (expected 1 to 100)").into());
+			}
+			let rgb = flatten_on_white(&img.to_rgba8());
+
+			let mut quality = quality;
+			loop {
+				let bytes = encode_jpeg(&rgb, quality)?;
+				let fits = max_bytes.is_none_or(|max| bytes.len() <= max);
+				if fits || quality <= MIN_JPEG_QUALITY {
+					return Ok(EncodedImage {
+						bytes,
+						quality: Some(quality),
+						fits,
+					});
+				}
+				quality = quality.saturating_sub(JPEG_QUALITY_STEP).max(MIN_JPEG_QUALITY);
+			}
+		}
+		ImageFormat::Webp => {
+			if quality.is_some() || max_bytes.is_some() {
+				return Err("--quality and --max-bytes do not apply to webp (lossless only), use --format jpeg".into());
+			}
+			Ok(EncodedImage {
+				bytes: encode_webp(&img.to_rgba8())?,
+				quality: None,
+				fits: true,
+			})
+		}
+	}
+}
+
+/// Encodes a lossless PNG, as small as possible:
+/// - indexed (palette) when 256 colors or fewer, with the smallest bit depth
+/// - RGB (no alpha channel) when fully opaque
+/// - maximum deflate compression
+pub fn optimize_png(img: &RgbaImage) -> Result<Vec<u8>> {
+	let (width, height) = img.dimensions();
+	let opaque = img.pixels().all(|p| p[3] == 255);
+
+	let mut buf = Vec::new();
+	let mut encoder = png::Encoder::new(&mut buf, width, height);
+	encoder.set_compression(png::Compression::High);
+
+	let data = match palette_of(img) {
+		Some(palette) => {
+			let bit_depth = match palette.len() {
+				0..=2 => png::BitDepth::One,
+				3..=4 => png::BitDepth::Two,
+				5..=16 => png::BitDepth::Four,
+				_ => png::BitDepth::Eight,
+			};
+			let mut entries: Vec<([u8; 4], u8)> = palette.iter().map(|(c, i)| (*c, *i)).collect();
+			entries.sort_by_key(|(_, i)| *i);
+
+			encoder.set_color(png::ColorType::Indexed);
+			encoder.set_depth(bit_depth);
+			encoder.set_palette(
+				entries
+					.iter()
+					.flat_map(|(c, _)| [c[0], c[1], c[2]])
+					.collect::<Vec<u8>>(),
+			);
+			if !opaque {
+				encoder.set_trns(entries.iter().map(|(c, _)| c[3]).collect::<Vec<u8>>());
+			}
+			pack_indices(img, &palette, bit_depth as u8)
+		}
+		None if opaque => {
+			encoder.set_color(png::ColorType::Rgb);
+			encoder.set_depth(png::BitDepth::Eight);
+			img.pixels().flat_map(|p| [p[0], p[1], p[2]]).collect()
+		}
+		None => {
+			encoder.set_color(png::ColorType::Rgba);
+			encoder.set_depth(png::BitDepth::Eight);
+			img.as_raw().clone()
+		}
+	};
+
+	let mut writer = encoder
+		.write_header()
+		.map_err(|e| format!("Cannot write PNG header. Cause: {e}"))?;
+	writer
+		.write_image_data(&data)
+		.map_err(|e| format!("Cannot write PNG data. Cause: {e}"))?;
+	writer.finish().map_err(|e| format!("Cannot finish PNG. Cause: {e}"))?;
+
+	Ok(buf)
+}
+
+/// The human readable byte size (e.g., `512 B`, `1.2 KB`, `3.4 MB`).
+pub fn format_bytes(size: usize) -> String {
+	const KB: f64 = 1024.;
+	let size_f = size as f64;
+	if size_f < KB {
+		format!("{size} B")
+	} else if size_f < KB * KB {
+		format!("{:.1} KB", size_f / KB)
+	} else {
+		format!("{:.1} MB", size_f / (KB * KB))
+	}
+}
+
+// region:    --- Support
+
+fn encode_jpeg(img: &RgbImage, quality: u8) -> Result<Vec<u8>> {
+	let mut buf = Vec::new();
+	JpegEncoder::new_with_quality(&mut buf, quality)
+		.encode_image(img)
+		.map_err(|e| format!("Cannot encode JPEG. Cause: {e}"))?;
+	Ok(buf)
+}
+
+fn encode_webp(img: &RgbaImage) -> Result<Vec<u8>> {
+	let mut buf = Vec::new();
+	WebPEncoder::new_lossless(&mut buf)
+		.write_image(img.as_raw(), img.width(), img.height(), ExtendedColorType::Rgba8)
+		.map_err(|e| format!("Cannot encode WebP. Cause: {e}"))?;
+	Ok(buf)
+}
+
+/// JPEG has no alpha, so transparent pixels are blended on white (rather than their hidden color).
+fn flatten_on_white(img: &RgbaImage) -> RgbImage {
+	RgbImage::from_fn(img.width(), img.height(), |x, y| {
+		let p = img.get_pixel(x, y);
+		let alpha = p[3] as u16;
+		let blend = |c: u8| ((c as u16 * alpha + 255 * (255 - alpha)) / 255) as u8;
+		image::Rgb([blend(p[0]), blend(p[1]), blend(p[2])])
+	})
+}
+
+/// The color => palette index map, or None when more than 256 colors.
+fn palette_of(img: &RgbaImage) -> Option<HashMap<[u8; 4], u8>> {
+	let mut palette: HashMap<[u8; 4], u8> = HashMap::new();
+	for pixel in img.pixels() {
+		if !palette.contains_key(&pixel.0) {
+			let index = u8::try_from(palette.len()).ok()?;
+			palette.insert(pixel.0, index);
+		}
+	}
+	Some(palette)
+}
+
+/// The palette indices packed per row at the bit depth (rows are byte aligned).
+fn pack_indices(img: &RgbaImage, palette: &HashMap<[u8; 4], u8>, bit_depth: u8) -> Vec<u8> {
+	let per_byte = (8 / bit_depth) as usize;
+	let row_len = (img.width() as usize).div_ceil(per_byte);
+
+	let mut data = Vec::with_capacity(row_len * img.height() as usize);
+	for row in img.rows() {
+		let mut row_bytes = vec![0u8; row_len];
+		for (x, pixel) in row.enumerate() {
+			let index = palette.get(&pixel.0).copied().unwrap_or_default();
+			let shift = 8 - bit_depth * (1 + (x % per_byte) as u8);
+			row_bytes[x / per_byte] |= index << shift;
+		}
+		data.extend(row_bytes);
+	}
+	data
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_support_images_encode_optimize_png_roundtrip() -> Result<()> {
+		// -- Setup & Fixtures
+		let colors = [[255, 0, 0, 255], [0, 128, 255, 255], [10, 20, 30, 0]];
+		let few_colors = RgbaImage::from_fn(7, 5, |x, y| image::Rgba(colors[((x + y) % 3) as usize]));
+		let many_colors = RgbaImage::from_fn(40, 40, |x, y| image::Rgba([x as u8 * 6, y as u8 * 6, 7, 255]));
+
+		// -- Exec
+		let few_png = optimize_png(&few_colors)?;
+		let many_png = optimize_png(&many_colors)?;
+		let jpeg = encode_image(
+			&DynamicImage::ImageRgba8(many_colors.clone()),
+			ImageFormat::Jpeg,
+			None,
+			Some(1),
+		)?;
+		let webp = encode_image(
+			&DynamicImage::ImageRgba8(few_colors.clone()),
+			ImageFormat::Webp,
+			None,
+			None,
+		)?;
+		let webp_quality_res = encode_image(
+			&DynamicImage::ImageRgba8(few_colors.clone()),
+			ImageFormat::Webp,
+			Some(80),
+			None,
+		);
+
+		// -- Check
+		let few_decoded = image::load_from_memory(&few_png)?.to_rgba8();
+		assert_eq!(few_decoded, few_colors, "palette PNG must be lossless");
+		let many_decoded = image::load_from_memory(&many_png)?;
+		assert!(!many_decoded.color().has_alpha(), "opaque PNG should drop the alpha");
+		assert_eq!(many_decoded.to_rgba8(), many_colors);
+		assert_eq!(jpeg.quality, Some(MIN_JPEG_QUALITY));
+		assert!(!jpeg.fits);
+		assert_eq!(
+			image::load_from_memory(&webp.bytes)?.to_rgba8(),
+			few_colors,
+			"WebP must be lossless"
+		);
+		assert!(webp_quality_res.is_err());
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,96 @@
+use std::path::{Component, Path, PathBuf};
+
+/// The Markdown image reference (e.g., `![my image](images/image-01.png)`).
+/// The path is wrapped in `<...>` when it has spaces or parentheses.
+pub fn md_image_ref(alt: &str, path: &str) -> String {
+	let alt = alt.replace('[', "\\[").replace(']', "\\]");
+	if path.contains([' ', '(', ')']) {
+		format!("![{alt}](<{path}>)")
+	} else {
+		format!("![{alt}]({path})")
+	}
+}
+
+/// The alt text derived from an image file name (e.g., `fig-003_setup.png` => `fig 003 setup`).
+pub fn alt_from_file_name(file_name: &str) -> String {
+	let stem = Path::new(file_name)
+		.file_stem()
+		.map(|s| s.to_string_lossy().to_string())
+		.unwrap_or_default();
+	stem.split(['-', '_', '.', ' '])
+		.filter(|w| !w.is_empty())
+		.collect::<Vec<_>>()
+		.join(" ")
+}
+
+/// The relative path (with `/`) from a directory to a path (both absolute, or both relative to the same dir).
+pub fn relative_path(from_dir: &Path, to: &Path) -> String {
+	let from = normalize(from_dir);
+	let to = normalize(to);
+
+	let common = from.iter().zip(to.iter()).take_while(|(a, b)| a == b).count();
+
+	let mut rel = PathBuf::new();
+	for _ in common..from.len() {
+		rel.push("..");
+	}
+	for component in &to[common..] {
+		rel.push(component);
+	}
+
+	rel.components()
+		.map(|c| c.as_os_str().to_string_lossy().to_string())
+		.collect::<Vec<_>>()
+		.join("/")
+}
+
+/// The path without `.` and with `..` resolved lexically (symlinks are not followed).
+pub fn normalize_path(path: &Path) -> PathBuf {
+	normalize(path).into_iter().collect()
+}
+
+/// The lexical components, without `.` and with `..` resolved when possible.
+fn normalize(path: &Path) -> Vec<Component<'_>> {
+	let mut components: Vec<Component> = Vec::new();
+	for component in path.components() {
+		match component {
+			Component::CurDir => {}
+			Component::ParentDir if matches!(components.last(), Some(Component::Normal(_))) => {
+				components.pop();
+			}
+			_ => components.push(component),
+		}
+	}
+	components
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_support_images_md_ref_relative_and_alt() -> Result<()> {
+		// -- Setup & Fixtures
+		let md_dir = Path::new("/notes/blog");
+
+		// -- Exec
+		let same_dir = relative_path(md_dir, Path::new("/notes/blog/image-01.png"));
+		let sub_dir = relative_path(md_dir, Path::new("/notes/blog/post.assets/image-01.png"));
+		let other_dir = relative_path(md_dir, Path::new("/notes/blog/../images/./fig-003.png"));
+		let with_space = md_image_ref(&alt_from_file_name("my shot_01.png"), "my dir/my shot_01.png");
+
+		// -- Check
+		assert_eq!(same_dir, "image-01.png");
+		assert_eq!(sub_dir, "post.assets/image-01.png");
+		assert_eq!(other_dir, "../images/fig-003.png");
+		assert_eq!(with_space, "![my shot 01](<my dir/my shot_01.png>)");
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
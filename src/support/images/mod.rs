@@ -0,0 +1,19 @@
+// region:    --- Modules
+
+mod dedup;
+mod encode;
+mod md_ref;
+mod naming;
+mod refs;
+mod renumber;
+mod transform;
+
+pub use dedup::*;
+pub use encode::*;
+pub use md_ref::*;
+pub use naming::*;
+pub use refs::*;
+pub use renumber::*;
+pub use transform::*;
+
+// endregion: --- Modules
@@ -0,0 +1,213 @@
+use crate::Result;
+use crate::support::text;
+use chrono::NaiveDateTime;
+use lazy_regex::regex;
+
+/// The default image name template (e.g., `image-01.png`).
+pub const DEFAULT_IMAGE_NAME_TEMPLATE: &str = "image-{n:02}";
+
+// region:    --- Types
+
+/// An image file name template (without extension), e.g., `{date}-post-{n:02}` or `fig-{n:03}`.
+///
+/// Placeholders:
+/// - `{n}`, `{n:03}`: the auto-incremented number (zero padded to the width)
+/// - `{date}` (`2026-10-18`), `{time}` (`143005`): the current local date and time
+/// - `{md_stem}`: the Markdown file stem, `{slug}`: the slug (default to the slugified Markdown file stem)
+#[derive(Debug)]
+pub struct NameTemplate {
+	parts: Vec<TemplatePart>,
+}
+
+#[derive(Debug, PartialEq)]
+enum TemplatePart {
+	Literal(String),
+	Index { width: usize },
+	Date,
+	Time,
+	MdStem,
+	Slug,
+}
+
+/// The values of the template placeholders (other than the number).
+#[derive(Debug)]
+pub struct NameContext {
+	pub now: NaiveDateTime,
+	pub md_stem: Option<String>,
+	pub slug: Option<String>,
+}
+
+// endregion: --- Types
+
+impl NameTemplate {
+	pub fn parse(template: &str) -> Result<Self> {
+		let re = regex!(r"\{([a-z_]+)(?::(\d+))?\}");
+
+		let mut parts = Vec::new();
+		let mut last_end = 0;
+		for caps in re.captures_iter(template) {
+			let Some(whole) = caps.get(0) else { continue };
+			if whole.start() > last_end {
+				parts.push(TemplatePart::Literal(template[last_end..whole.start()].to_string()));
+			}
+			let width = caps
+				.get(2)
+				.map(|w| w.as_str().parse::<usize>())
+				.transpose()
+				.map_err(|e| format!("Invalid number width in '{}'. Cause: {e}", whole.as_str()))?;
+			let part = match (&caps[1], width) {
+				("n", width) => TemplatePart::Index {
+					width: width.unwrap_or(0),
+				},
+				("date", None) => TemplatePart::Date,
+				("time", None) => TemplatePart::Time,
+				("md_stem", None) => TemplatePart::MdStem,
+				("slug", None) => TemplatePart::Slug,
+				_ => {
+					return Err(format!(
+						"Unknown placeholder '{}' in the name template '{template}'",
+						whole.as_str()
+					)
+					.into());
+				}
+			};
+			parts.push(part);
+			last_end = whole.end();
+		}
+		if last_end < template.len() {
+			parts.push(TemplatePart::Literal(template[last_end..].to_string()));
+		}
+
+		if parts.is_empty() {
+			return Err("The image name template cannot be empty".into());
+		}
+		let has_invalid_char = parts
+			.iter()
+			.any(|p| matches!(p, TemplatePart::Literal(l) if l.contains(['/', '\\', '{', '}'])));
+		if has_invalid_char {
+			return Err(
+				format!("Invalid image name template '{template}' (no '/', '\\', or unknown '{{...}}')").into(),
+			);
+		}
+
+		Ok(Self { parts })
+	}
+
+	/// The template has a `{n}` placeholder.
+	pub fn has_index(&self) -> bool {
+		self.parts.iter().any(|p| matches!(p, TemplatePart::Index { .. }))
+	}
+
+	/// Render the file name (with the extension) for the number `n`.
+	pub fn render(&self, ctx: &NameContext, n: u32, ext: &str) -> Result<String> {
+		let mut name = String::new();
+		for part in self.parts.iter() {
+			match part {
+				TemplatePart::Index { width } => name.push_str(&format!("{n:0width$}")),
+				other => name.push_str(&self.render_fixed(other, ctx)?),
+			}
+		}
+		Ok(format!("{name}.{ext}"))
+	}
+
+	/// The next file name: one more than the highest number of the existing files matching the template
+	/// (any image extension), or the name as is when no `{n}` (fails if it already exists).
+	pub fn next_name(&self, ctx: &NameContext, existing: &[String], ext: &str) -> Result<String> {
+		// -- Regex of the file names (all placeholders but the number are fixed)
+		let mut pattern = String::from("^");
+		for part in self.parts.iter() {
+			match part {
+				TemplatePart::Index { .. } => pattern.push_str(r"(\d+)"),
+				other => pattern.push_str(&regex::escape(&self.render_fixed(other, ctx)?)),
+			}
+		}
+		pattern.push_str(r"\.[A-Za-z0-9]+$");
+		let re = regex::Regex::new(&pattern).map_err(|e| format!("Invalid image name template regex. Cause: {e}"))?;
+
+		if !self.has_index() {
+			let name = self.render(ctx, 0, ext)?;
+			if let Some(existing) = existing.iter().find(|f| re.is_match(f)) {
+				return Err(format!("Image '{existing}' already exists (add '{{n}}' to the name template)").into());
+			}
+			return Ok(name);
+		}
+
+		let max_n = existing
+			.iter()
+			.filter_map(|f| re.captures(f)?.get(1)?.as_str().parse::<u32>().ok())
+			.max()
+			.unwrap_or(0);
+
+		self.render(ctx, max_n + 1, ext)
+	}
+
+	fn render_fixed(&self, part: &TemplatePart, ctx: &NameContext) -> Result<String> {
+		let value = match part {
+			TemplatePart::Literal(literal) => literal.clone(),
+			TemplatePart::Index { .. } => String::new(),
+			TemplatePart::Date => ctx.now.format("%Y-%m-%d").to_string(),
+			TemplatePart::Time => ctx.now.format("%H%M%S").to_string(),
+			TemplatePart::MdStem => ctx
+				.md_stem
+				.clone()
+				.ok_or("'{md_stem}' needs a Markdown file (ZED_FILE or --for-file)")?,
+			TemplatePart::Slug => match (&ctx.slug, &ctx.md_stem) {
+				(Some(slug), _) => text::slugify(slug, '-'),
+				(None, Some(md_stem)) => text::slugify(md_stem, '-'),
+				(None, None) => return Err("'{slug}' needs --slug or a Markdown file (ZED_FILE)".into()),
+			},
+		};
+		Ok(value)
+	}
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+	use chrono::NaiveDate;
+
+	#[test]
+	fn test_support_images_naming_next_name_simple() -> Result<()> {
+		// -- Setup & Fixtures
+		let ctx = NameContext {
+			now: NaiveDate::from_ymd_opt(2026, 10, 18)
+				.and_then(|d| d.and_hms_opt(14, 30, 5))
+				.ok_or("bad date")?,
+			md_stem: Some("My Post".to_string()),
+			slug: None,
+		};
+		let existing: Vec<String> = [
+			"2026-10-18-post-01.png",
+			"2026-10-18-post-07.jpg",
+			"2026-10-17-post-09.png",
+			"fig-002.png",
+		]
+		.iter()
+		.map(|s| s.to_string())
+		.collect();
+
+		// -- Exec
+		let post = NameTemplate::parse("{date}-post-{n:02}")?.next_name(&ctx, &existing, "png")?;
+		let fig = NameTemplate::parse("fig-{n:03}")?.next_name(&ctx, &existing, "png")?;
+		let slug = NameTemplate::parse("{slug}-{time}-{n}")?.next_name(&ctx, &existing, "png")?;
+		let default = NameTemplate::parse(DEFAULT_IMAGE_NAME_TEMPLATE)?.next_name(&ctx, &existing, "png")?;
+		let no_index = NameTemplate::parse("fig-002")?.next_name(&ctx, &existing, "png");
+		let unknown = NameTemplate::parse("img-{nn}");
+
+		// -- Check
+		assert_eq!(post, "2026-10-18-post-08.png");
+		assert_eq!(fig, "fig-003.png");
+		assert_eq!(slug, "my-post-143005-1.png");
+		assert_eq!(default, "image-01.png");
+		assert!(no_index.is_err());
+		assert!(unknown.is_err());
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
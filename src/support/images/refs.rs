@@ -0,0 +1,192 @@
+use crate::support::images::normalize_path;
+use crate::support::text;
+use lazy_regex::regex;
+use pulldown_cmark::{Event, Options, Parser, Tag};
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+// region:    --- Types
+
+/// An image reference in a Markdown file, from a Markdown image or a raw HTML `<img src>`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct MdImageRef {
+	pub url: String,
+	/// 1-based line in the Markdown file.
+	pub line: usize,
+	/// The byte range of the raw URL in the content (None for a reference style image, e.g., `![alt][fig]`).
+	pub url_range: Option<Range<usize>>,
+}
+
+// endregion: --- Types
+
+/// The image references of a Markdown content, in document order.
+pub fn md_image_refs(content: &str) -> Vec<MdImageRef> {
+	let img_src_re = regex!(r#"(?i)<img\b[^>]*?\bsrc\s*=\s*(?:"([^"]*)"|'([^']*)'|([^\s>]+))"#);
+
+	let mut options = Options::empty();
+	options.insert(Options::ENABLE_TABLES);
+	options.insert(Options::ENABLE_FOOTNOTES);
+
+	let mut refs = Vec::new();
+	for (event, range) in Parser::new_ext(content, options).into_offset_iter() {
+		match event {
+			Event::Start(Tag::Image { dest_url, .. }) => refs.push(MdImageRef {
+				url: dest_url.to_string(),
+				line: text::line_col(content, range.start).0,
+				url_range: raw_url_range(content, range, "]("),
+			}),
+			Event::Html(html) | Event::InlineHtml(html) => {
+				for caps in img_src_re.captures_iter(&html) {
+					let Some(src) = caps.get(1).or(caps.get(2)).or(caps.get(3)) else {
+						continue;
+					};
+					// Note: The HTML event text is the source text, so the offsets map back to the content
+					let url_range = range.start + src.start()..range.start + src.end();
+					refs.push(MdImageRef {
+						url: src.as_str().to_string(),
+						line: text::line_col(content, url_range.start).0,
+						url_range: Some(url_range),
+					});
+				}
+			}
+			_ => {}
+		}
+	}
+
+	refs
+}
+
+/// The URLs of the link reference definitions (e.g., `[fig]: images/image-01.png`), which can be image references.
+pub fn md_link_def_urls(content: &str) -> Vec<MdImageRef> {
+	let parser = Parser::new_ext(content, Options::empty());
+	let mut defs: Vec<MdImageRef> = parser
+		.reference_definitions()
+		.iter()
+		.map(|(_, def)| MdImageRef {
+			url: def.dest.to_string(),
+			line: text::line_col(content, def.span.start).0,
+			url_range: raw_url_range(content, def.span.clone(), "]:"),
+		})
+		.collect();
+	defs.sort_by_key(|def| def.line);
+	defs
+}
+
+/// The local file of an image reference, or None for an external one (e.g., `https://`, `data:`).
+/// - Relative to the Markdown file dir, and `/...` relative to the root dir.
+/// - The `?query` and `#fragment` are removed, and the `%XX` escapes decoded.
+pub fn resolve_image_ref(url: &str, md_dir: &Path, root_dir: &Path) -> Option<PathBuf> {
+	let url = url.trim();
+	let is_external = url.is_empty() || url.starts_with("//") || regex!(r"^[a-zA-Z][a-zA-Z0-9+.-]*:").is_match(url);
+	if is_external {
+		return None;
+	}
+
+	let path = url.split(['?', '#']).next().unwrap_or_default();
+	if path.is_empty() {
+		return None;
+	}
+	let path = text::percent_decode(path);
+
+	let full_path = match path.strip_prefix('/') {
+		Some(rooted) => root_dir.join(rooted),
+		None => md_dir.join(path),
+	};
+	Some(normalize_path(&full_path))
+}
+
+// region:    --- Support
+
+/// The range of the raw URL after the marker (e.g., `](` of an inline image), with or without `<...>`.
+fn raw_url_range(content: &str, range: Range<usize>, marker: &str) -> Option<Range<usize>> {
+	let text = content.get(range.clone())?;
+	let after_marker = text.find(marker)? + marker.len();
+	let rest = &text[after_marker..];
+	let trimmed = rest.trim_start();
+	let mut start = range.start + after_marker + (rest.len() - trimmed.len());
+
+	let len = match trimmed.strip_prefix('<') {
+		Some(inner) => {
+			start += 1;
+			inner.find('>')?
+		}
+		None => trimmed
+			.find(|c: char| c.is_whitespace() || c == ')')
+			.unwrap_or(trimmed.len()),
+	};
+	Some(start..start + len)
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_support_images_refs_md_image_refs_and_resolve() -> Result<()> {
+		// -- Setup & Fixtures
+		let content = r#"# Post
+
+![first](images/image-01.png "title") and ![remote](https://example.com/a.png)
+
+<p><img alt="x" src='../shared/my%20shot.png?v=2'></p>
+
+Inline <img src="/assets/logo.png"> and `![code](not-an-image.png)`
+
+![ref][fig]
+
+[fig]: images/image-01.png
+"#;
+		let md_dir = Path::new("/root/docs/blog");
+		let root_dir = Path::new("/root/docs");
+
+		// -- Exec
+		let refs = md_image_refs(content);
+		let defs = md_link_def_urls(content);
+		let resolved: Vec<Option<PathBuf>> = refs
+			.iter()
+			.map(|r| resolve_image_ref(&r.url, md_dir, root_dir))
+			.collect();
+
+		// -- Check
+		let urls_lines: Vec<(&str, usize)> = refs.iter().map(|r| (r.url.as_str(), r.line)).collect();
+		assert_eq!(
+			urls_lines,
+			vec![
+				("images/image-01.png", 3),
+				("https://example.com/a.png", 3),
+				("../shared/my%20shot.png?v=2", 5),
+				("/assets/logo.png", 7),
+				("images/image-01.png", 9),
+			]
+		);
+		let raw_urls: Vec<Option<&str>> = refs
+			.iter()
+			.map(|r| r.url_range.clone().map(|range| &content[range]))
+			.collect();
+		assert_eq!(raw_urls[0], Some("images/image-01.png"));
+		assert_eq!(raw_urls[2], Some("../shared/my%20shot.png?v=2"));
+		assert_eq!(raw_urls[4], None);
+		assert_eq!(defs.len(), 1);
+		assert_eq!(
+			defs[0].url_range.clone().map(|range| &content[range]),
+			Some("images/image-01.png")
+		);
+		assert_eq!(
+			resolved[0].as_deref(),
+			Some(Path::new("/root/docs/blog/images/image-01.png"))
+		);
+		assert_eq!(resolved[1], None);
+		assert_eq!(resolved[2].as_deref(), Some(Path::new("/root/docs/shared/my shot.png")));
+		assert_eq!(resolved[3].as_deref(), Some(Path::new("/root/docs/assets/logo.png")));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
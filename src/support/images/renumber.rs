@@ -0,0 +1,191 @@
+use crate::support::images::{md_image_refs, md_link_def_urls, resolve_image_ref};
+use lazy_regex::regex;
+use std::collections::HashMap;
+use std::ops::Range;
+use std::path::{Path, PathBuf};
+
+/// The renames (old => new file name) numbering the referenced `image-NN.png` files in order of first reference.
+/// The unreferenced ones (maybe referenced by other Markdown files) keep their names, and their numbers are skipped.
+/// The numbers keep the widest existing padding (e.g., `image-001.png`), 2 digits at least.
+/// Unchanged names are not included.
+pub fn renumber_plan(file_names: &[String], first_refs: &[String]) -> Vec<(String, String)> {
+	let numbered: Vec<(u64, &String)> = file_names
+		.iter()
+		.filter_map(|name| Some((image_number(name)?, name)))
+		.collect();
+
+	let mut ordered: Vec<&String> = Vec::new();
+	for name in first_refs {
+		if numbered.iter().any(|(_, n)| *n == name) && !ordered.contains(&name) {
+			ordered.push(name);
+		}
+	}
+	let kept_numbers: Vec<u64> = numbered
+		.iter()
+		.filter(|(_, name)| !ordered.contains(name))
+		.map(|(number, _)| *number)
+		.collect();
+
+	let mut numbers = (1..).filter(|number| !kept_numbers.contains(number));
+	let new_numbers: Vec<u64> = ordered.iter().map(|_| numbers.next().unwrap_or_default()).collect();
+	let existing_width = numbered
+		.iter()
+		.map(|(_, name)| name.len() - "image-.png".len())
+		.max()
+		.unwrap_or_default();
+	let width = new_numbers
+		.iter()
+		.max()
+		.unwrap_or(&0)
+		.to_string()
+		.len()
+		.max(existing_width)
+		.max(2);
+	ordered
+		.into_iter()
+		.zip(new_numbers)
+		.map(|(name, number)| (name.clone(), format!("image-{number:0width$}.png")))
+		.filter(|(old, new)| old != new)
+		.collect()
+}
+
+/// The number of an `image-NN.png` file name.
+pub fn image_number(file_name: &str) -> Option<u64> {
+	regex!(r"^image-(\d+)\.png$").captures(file_name)?[1].parse().ok()
+}
+
+/// The content with the renamed image file names in the URLs of the Markdown images, HTML `<img src>`,
+/// and link reference definitions, and the number of rewritten references.
+/// The `renames` keys are the normalized absolute paths of the old files.
+pub fn rewrite_image_refs(
+	content: &str,
+	md_dir: &Path,
+	root_dir: &Path,
+	renames: &HashMap<PathBuf, String>,
+) -> (String, usize) {
+	let mut edits: Vec<(Range<usize>, String)> = Vec::new();
+	for image_ref in md_image_refs(content).into_iter().chain(md_link_def_urls(content)) {
+		let Some(url_range) = image_ref.url_range else {
+			continue;
+		};
+		let Some(new_name) = resolve_image_ref(&image_ref.url, md_dir, root_dir).and_then(|path| renames.get(&path))
+		else {
+			continue;
+		};
+		// The file name is the last path segment of the raw URL (before the `?query` or `#fragment`)
+		let raw_url = &content[url_range.clone()];
+		let path_len = raw_url.find(['?', '#']).unwrap_or(raw_url.len());
+		let name_start = raw_url[..path_len].rfind('/').map(|i| i + 1).unwrap_or(0);
+		let name_range = url_range.start + name_start..url_range.start + path_len;
+		if !edits.iter().any(|(range, _)| *range == name_range) {
+			edits.push((name_range, new_name.clone()));
+		}
+	}
+
+	edits.sort_by_key(|(range, _)| range.start);
+	let mut new_content = String::with_capacity(content.len());
+	let mut last_end = 0;
+	for (range, new_name) in edits.iter() {
+		new_content.push_str(&content[last_end..range.start]);
+		new_content.push_str(new_name);
+		last_end = range.end;
+	}
+	new_content.push_str(&content[last_end..]);
+
+	(new_content, edits.len())
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_support_images_renumber_plan_and_rewrite() -> Result<()> {
+		// -- Setup & Fixtures
+		let names: Vec<String> = [
+			"image-02.png",
+			"image-05.png",
+			"image-07.png",
+			"image-09.png",
+			"logo.png",
+		]
+		.iter()
+		.map(|name| name.to_string())
+		.collect();
+		let first_refs: Vec<String> = [
+			"image-07.png",
+			"logo.png",
+			"image-02.png",
+			"image-07.png",
+			"image-09.png",
+		]
+		.iter()
+		.map(|name| name.to_string())
+		.collect();
+		let content = r#"![b](images/image-07.png "image-07.png")
+<img src="images/image-02.png?v=1">
+![a][fig] and ![c](images/image-07.png)
+
+[fig]: ./images/image-09.png
+"#;
+		let md_dir = Path::new("/docs");
+
+		// -- Exec
+		let plan = renumber_plan(&names, &first_refs);
+		let renames: HashMap<PathBuf, String> = plan
+			.iter()
+			.map(|(old, new)| (md_dir.join("images").join(old), new.clone()))
+			.collect();
+		let (new_content, count) = rewrite_image_refs(content, md_dir, md_dir, &renames);
+
+		// -- Check
+		let plan: Vec<(&str, &str)> = plan.iter().map(|(old, new)| (old.as_str(), new.as_str())).collect();
+		assert_eq!(
+			plan,
+			vec![
+				("image-07.png", "image-01.png"),
+				// image-05.png is not referenced, so it is kept (and 05 is skipped)
+				("image-09.png", "image-03.png"),
+			]
+		);
+		// the unreferenced image-01.png keeps its number
+		let kept_plan = renumber_plan(
+			&["image-01.png".to_string(), "image-03.png".to_string()],
+			&["image-03.png".to_string()],
+		);
+		assert_eq!(
+			kept_plan,
+			vec![("image-03.png".to_string(), "image-02.png".to_string())]
+		);
+		// the existing padding is kept
+		let padded_plan = renumber_plan(
+			&["image-003.png".to_string(), "image-010.png".to_string()],
+			&["image-010.png".to_string(), "image-003.png".to_string()],
+		);
+		assert_eq!(
+			padded_plan,
+			vec![
+				("image-010.png".to_string(), "image-001.png".to_string()),
+				("image-003.png".to_string(), "image-002.png".to_string()),
+			]
+		);
+		assert_eq!(count, 3);
+		assert_eq!(
+			new_content,
+			r#"![b](images/image-01.png "image-07.png")
+<img src="images/image-02.png?v=1">
+![a][fig] and ![c](images/image-01.png)
+
+[fig]: ./images/image-03.png
+"#
+		);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,278 @@
+use crate::Result;
+use image::imageops::{self, FilterType};
+use image::{Rgba, RgbaImage};
+
+/// The default `--border-color` (light gray).
+pub const DEFAULT_BORDER_COLOR: &str = "#d0d7de";
+
+/// The shadow opacity (0 to 255) before the blur.
+const SHADOW_ALPHA: u8 = 90;
+
+/// The largest transformed width or height (e.g., against a huge `--scale` or `--pad`).
+const MAX_DIMENSION: u32 = 16_384;
+
+// region:    --- Types
+
+/// The image transforms, applied in order: trim, scale, fit (max width/height), pad, border, shadow.
+#[derive(Debug, Clone)]
+pub struct ImageTransforms {
+	/// Trim the uniform borders (the top-left pixel color), with this per channel tolerance.
+	pub trim: Option<u8>,
+	pub scale: Option<f32>,
+	pub max_width: Option<u32>,
+	pub max_height: Option<u32>,
+	pub pad: u32,
+	pub pad_color: Rgba<u8>,
+	pub border: u32,
+	pub border_color: Rgba<u8>,
+	pub shadow: u32,
+}
+
+impl Default for ImageTransforms {
+	fn default() -> Self {
+		Self {
+			trim: None,
+			scale: None,
+			max_width: None,
+			max_height: None,
+			pad: 0,
+			pad_color: Rgba([0, 0, 0, 0]),
+			border: 0,
+			border_color: parse_hex_color(DEFAULT_BORDER_COLOR).expect("DEFAULT_BORDER_COLOR should be a valid color"),
+			shadow: 0,
+		}
+	}
+}
+
+// endregion: --- Types
+
+impl ImageTransforms {
+	pub fn is_empty(&self) -> bool {
+		self.trim.is_none()
+			&& self.scale.is_none()
+			&& self.max_width.is_none()
+			&& self.max_height.is_none()
+			&& self.pad == 0
+			&& self.border == 0
+			&& self.shadow == 0
+	}
+
+	pub fn apply(&self, img: RgbaImage) -> Result<RgbaImage> {
+		let mut img = img;
+		if let Some(tolerance) = self.trim {
+			img = trim(&img, tolerance);
+		}
+		if let Some(factor) = self.scale {
+			img = scale(&img, factor)?;
+		}
+		if self.max_width.is_some() || self.max_height.is_some() {
+			img = fit_within(&img, self.max_width, self.max_height);
+		}
+		if self.pad > 0 {
+			img = frame(&img, self.pad, self.pad_color)?;
+		}
+		if self.border > 0 {
+			img = frame(&img, self.border, self.border_color)?;
+		}
+		if self.shadow > 0 {
+			img = drop_shadow(&img, self.shadow)?;
+		}
+		Ok(img)
+	}
+}
+
+/// Crops the borders of the top-left pixel color (each channel within the tolerance).
+/// A uniform image is returned as is.
+pub fn trim(img: &RgbaImage, tolerance: u8) -> RgbaImage {
+	let (width, height) = img.dimensions();
+	if width == 0 || height == 0 {
+		return img.clone();
+	}
+	let background = *img.get_pixel(0, 0);
+	let is_background = |p: &Rgba<u8>| {
+		p.0.iter()
+			.zip(background.0.iter())
+			.all(|(a, b)| a.abs_diff(*b) <= tolerance)
+	};
+
+	let mut bounds: Option<(u32, u32, u32, u32)> = None;
+	for (x, y, pixel) in img.enumerate_pixels() {
+		if is_background(pixel) {
+			continue;
+		}
+		bounds = Some(match bounds {
+			Some((x0, y0, x1, y1)) => (x0.min(x), y0.min(y), x1.max(x), y1.max(y)),
+			None => (x, y, x, y),
+		});
+	}
+
+	match bounds {
+		Some((x0, y0, x1, y1)) => imageops::crop_imm(img, x0, y0, x1 - x0 + 1, y1 - y0 + 1).to_image(),
+		None => img.clone(),
+	}
+}
+
+/// Resizes by a factor (e.g., `0.5` for HiDPI captures).
+pub fn scale(img: &RgbaImage, factor: f32) -> Result<RgbaImage> {
+	if !(factor > 0. && factor.is_finite()) {
+		return Err(format!("Invalid --scale {factor} (expected a number greater than 0)").into());
+	}
+	let (width, height) = img.dimensions();
+	let scaled_checked = |size: u32| {
+		let size = (size as f64 * factor as f64).round();
+		(size <= MAX_DIMENSION as f64).then(|| (size as u32).max(1))
+	};
+	let (new_width, new_height) = checked_dimensions(
+		scaled_checked(width),
+		scaled_checked(height),
+		&format!("--scale {factor} of {width}x{height}"),
+	)?;
+	Ok(resize(img, new_width, new_height))
+}
+
+/// Downscales (keeping the aspect ratio) to fit within the max width and height.
+pub fn fit_within(img: &RgbaImage, max_width: Option<u32>, max_height: Option<u32>) -> RgbaImage {
+	let (width, height) = img.dimensions();
+	let ratio_w = max_width.map(|max| max as f32 / width.max(1) as f32).unwrap_or(1.);
+	let ratio_h = max_height.map(|max| max as f32 / height.max(1) as f32).unwrap_or(1.);
+	let ratio = ratio_w.min(ratio_h);
+	if ratio >= 1. {
+		return img.clone();
+	}
+	resize(img, scaled(width, ratio), scaled(height, ratio))
+}
+
+/// Surrounds the image with a frame of the color (padding or border).
+pub fn frame(img: &RgbaImage, size: u32, color: Rgba<u8>) -> Result<RgbaImage> {
+	let (width, height) = framed_dimensions(img, size, "frame")?;
+	let mut canvas = RgbaImage::from_pixel(width, height, color);
+	imageops::replace(&mut canvas, img, size as i64, size as i64);
+	Ok(canvas)
+}
+
+/// Adds a soft drop shadow (to the bottom right) on a transparent canvas, with a margin of the size.
+pub fn drop_shadow(img: &RgbaImage, size: u32) -> Result<RgbaImage> {
+	let (width, height) = img.dimensions();
+	let offset = (size / 2).max(1);
+
+	let (canvas_width, canvas_height) = framed_dimensions(img, size, "shadow")?;
+	let mut canvas = RgbaImage::new(canvas_width, canvas_height);
+	for y in 0..height {
+		for x in 0..width {
+			canvas.put_pixel(x + size + offset, y + size + offset, Rgba([0, 0, 0, SHADOW_ALPHA]));
+		}
+	}
+	// Note: The canvas margin is larger than the offset, so the shadow stays inside
+	let mut canvas = imageops::fast_blur(&canvas, size as f32 / 3.);
+	imageops::overlay(&mut canvas, img, size as i64, size as i64);
+	Ok(canvas)
+}
+
+/// Parses `#rrggbb` or `#rrggbbaa` (the `#` is optional).
+pub fn parse_hex_color(color: &str) -> Result<Rgba<u8>> {
+	let hex = color.trim().trim_start_matches('#');
+	let channel = |i: usize| {
+		hex.get(i..i + 2)
+			.and_then(|c| u8::from_str_radix(c, 16).ok())
+			.ok_or_else(|| format!("Invalid color '{color}' (expected #rrggbb or #rrggbbaa)"))
+	};
+	match hex.len() {
+		6 => Ok(Rgba([channel(0)?, channel(2)?, channel(4)?, 0xff])),
+		8 => Ok(Rgba([channel(0)?, channel(2)?, channel(4)?, channel(6)?])),
+		_ => Err(format!("Invalid color '{color}' (expected #rrggbb or #rrggbbaa)").into()),
+	}
+}
+
+// region:    --- Support
+
+fn scaled(size: u32, factor: f32) -> u32 {
+	((size as f32 * factor).round() as u32).max(1)
+}
+
+/// The image dimensions with a margin of the size on each side.
+fn framed_dimensions(img: &RgbaImage, size: u32, what: &str) -> Result<(u32, u32)> {
+	let (width, height) = img.dimensions();
+	let framed = |side: u32| size.checked_mul(2).and_then(|margins| side.checked_add(margins));
+	checked_dimensions(
+		framed(width),
+		framed(height),
+		&format!("{size}px {what} around {width}x{height}"),
+	)
+}
+
+/// The dimensions when computed (no overflow) and within the max, else an error.
+fn checked_dimensions(width: Option<u32>, height: Option<u32>, description: &str) -> Result<(u32, u32)> {
+	match (width, height) {
+		(Some(width), Some(height)) if width <= MAX_DIMENSION && height <= MAX_DIMENSION => Ok((width, height)),
+		_ => Err(format!("Transformed image too large: {description} (max {MAX_DIMENSION}x{MAX_DIMENSION})").into()),
+	}
+}
+
+fn resize(img: &RgbaImage, width: u32, height: u32) -> RgbaImage {
+	if img.dimensions() == (width, height) {
+		return img.clone();
+	}
+	imageops::resize(img, width, height, FilterType::Lanczos3)
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_support_images_transform_apply_pipeline() -> Result<()> {
+		// -- Setup & Fixtures
+		// 100x60 white, with a 40x20 blue box at (30, 10), and a near white noise pixel
+		let blue = Rgba([0, 0, 255, 255]);
+		let mut img = RgbaImage::from_pixel(100, 60, Rgba([255, 255, 255, 255]));
+		for y in 10..30 {
+			for x in 30..70 {
+				img.put_pixel(x, y, blue);
+			}
+		}
+		img.put_pixel(2, 50, Rgba([250, 252, 255, 255]));
+		let transforms = ImageTransforms {
+			trim: Some(8),
+			scale: Some(0.5),
+			pad: 3,
+			border: 1,
+			border_color: parse_hex_color("#ff0000")?,
+			..Default::default()
+		};
+
+		// -- Exec
+		let trimmed = trim(&img, 8);
+		let framed = transforms.apply(img.clone())?;
+		let fitted = fit_within(&img, Some(50), Some(50));
+		let shadowed = drop_shadow(&trimmed, 6)?;
+		let huge_scale = scale(&img, 1e30);
+		let huge_pad = frame(&img, u32::MAX, blue);
+
+		// -- Check
+		assert_eq!(trimmed.dimensions(), (40, 20));
+		assert!(trimmed.pixels().all(|p| *p == blue));
+		// 40x20 trimmed => 20x10 scaled => + 2 * (3 + 1)
+		assert_eq!(framed.dimensions(), (28, 18));
+		assert_eq!(*framed.get_pixel(0, 0), Rgba([255, 0, 0, 255]));
+		assert_eq!(*framed.get_pixel(1, 1), Rgba([0, 0, 0, 0]));
+		assert_eq!(framed.get_pixel(14, 9)[2], 255);
+		assert_eq!(fitted.dimensions(), (50, 30));
+		assert_eq!(shadowed.dimensions(), (52, 32));
+		assert_eq!(*shadowed.get_pixel(6, 6), blue);
+		assert_eq!(shadowed.get_pixel(0, 0)[3], 0);
+		assert!(shadowed.get_pixel(47, 27)[3] > 0, "shadow expected at the bottom right");
+		assert!(parse_hex_color("#12345").is_err());
+		assert!(huge_scale.is_err());
+		assert!(huge_pad.is_err());
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -1,5 +1,7 @@
 use crate::{Error, Result};
-use jsonc_parser::ParseOptions;
+use jsonc_parser::ast;
+use jsonc_parser::cst::{CstInputValue, CstObject, CstRootNode};
+use jsonc_parser::{CollectOptions, CommentCollectionStrategy, ParseOptions};
 use lazy_regex::regex;
 use serde_json::Value;
 use simple_fs::SPath;
@@ -7,24 +9,24 @@ use std::borrow::Cow;
 
 // region:    --- JSONC Parser
 
+static JSONC_OPTIONS: ParseOptions = ParseOptions {
+	allow_comments: true,
+	allow_trailing_commas: true,
+	// this one is set to FALSE, for better IDE compatibility
+	allow_loose_object_property_names: false,
+	allow_single_quoted_strings: false,
+	allow_hexadecimal_numbers: false,
+	allow_unary_plus_numbers: false,
+	allow_missing_commas: false,
+};
+
 // Prase a json string content that can have
 /// - Comments
 /// - Trailing commas
 ///
 /// Note: Property names still need to be quoted.
 pub fn parse_jsonc_to_serde_value(content: &str) -> Result<Option<serde_json::Value>> {
-	static OPTIONS: ParseOptions = ParseOptions {
-		allow_comments: true,
-		allow_trailing_commas: true,
-		// this one is set to FALSE, for better IDE compatibility
-		allow_loose_object_property_names: false,
-		allow_single_quoted_strings: false,
-		allow_hexadecimal_numbers: false,
-		allow_unary_plus_numbers: false,
-		allow_missing_commas: false,
-	};
-
-	let json_value = jsonc_parser::parse_to_serde_value(content, &OPTIONS).map_err(|err| {
+	let json_value = jsonc_parser::parse_to_serde_value(content, &JSONC_OPTIONS).map_err(|err| {
 		let content = truncate_with_ellipsis(content, 300, "...");
 		Error::custom(format!("Fail to parse json.\nCause: {err}\nJson Content:\n{content}"))
 	})?;
@@ -41,17 +43,100 @@ pub fn load_jsons_to_serde_value(file: &SPath) -> Result<Option<serde_json::Valu
 	Ok(value)
 }
 
+/// Parse a jsonc content into an AST (with the byte ranges of the nodes, e.g., for line/column reporting).
+pub fn parse_jsonc_to_ast(content: &str) -> Result<Option<ast::Value<'_>>> {
+	let collect_options = CollectOptions {
+		comments: CommentCollectionStrategy::Off,
+		tokens: false,
+	};
+	let parse_result = jsonc_parser::parse_to_ast(content, &collect_options, &JSONC_OPTIONS).map_err(|err| {
+		let content = truncate_with_ellipsis(content, 300, "...");
+		Error::custom(format!("Fail to parse json.\nCause: {err}\nJson Content:\n{content}"))
+	})?;
+
+	Ok(parse_result.value)
+}
+
 // endregion: --- JSONC Parser
 
+// region:    --- JSONC CST
+
+/// Parse a jsonc content into a CST (Concrete Syntax Tree) that can be edited
+/// while preserving comments and formatting.
+///
+/// Note: An empty content gives a root without value (use `array_value_or_set` / `object_value_or_set`).
+pub fn parse_jsonc_to_cst(content: &str) -> Result<CstRootNode> {
+	let root = CstRootNode::parse(content, &JSONC_OPTIONS).map_err(|err| {
+		let content = truncate_with_ellipsis(content, 300, "...");
+		Error::custom(format!("Fail to parse json.\nCause: {err}\nJson Content:\n{content}"))
+	})?;
+
+	Ok(root)
+}
+
+/// Set (or add) the value at the `prop_path` of a jsonc content (comments and formatting preserved).
+/// Missing intermediate objects are created.
+pub fn set_value_cst(content: &str, prop_path: &[&str], value: &Value) -> Result<String> {
+	let Some((key, parent_path)) = prop_path.split_last() else {
+		return Err(Error::custom("prop_path cannot be empty"));
+	};
+
+	let root = parse_jsonc_to_cst(content)?;
+	let mut obj = root.object_value_or_create().ok_or("Json root must be an object")?;
+	for name in parent_path {
+		obj = obj
+			.object_value_or_create(name)
+			.ok_or_else(|| format!("Json property '{name}' is not an object"))?;
+	}
+
+	let input = serde_value_to_cst_input(value);
+	match obj.get(key) {
+		Some(prop) => prop.set_value(input),
+		None => {
+			obj.append(key, input);
+		}
+	}
+
+	Ok(root.to_string())
+}
+
+/// Convert a serde_json value to a CST input value (for insert/replace in the CST).
+pub fn serde_value_to_cst_input(value: &Value) -> CstInputValue {
+	match value {
+		Value::Null => CstInputValue::Null,
+		Value::Bool(b) => CstInputValue::Bool(*b),
+		Value::Number(n) => CstInputValue::Number(n.to_string()),
+		Value::String(s) => CstInputValue::String(s.clone()),
+		Value::Array(items) => CstInputValue::Array(items.iter().map(serde_value_to_cst_input).collect()),
+		Value::Object(map) => CstInputValue::Object(
+			map.iter()
+				.map(|(k, v)| (k.clone(), serde_value_to_cst_input(v)))
+				.collect(),
+		),
+	}
+}
+
+/// Returns the decoded string value of the `name` property of a CST object (if it is a string).
+pub fn cst_object_str_prop(obj: &CstObject, name: &str) -> Option<String> {
+	let value = obj.get(name)?.value()?;
+	value.as_string_lit()?.decoded_value().ok()
+}
+
+// endregion: --- JSONC CST
+
+/// The byte ranges of the `"key": value` matches of the text-mode update (`update_json_value_text_mode`),
+/// for a value that is a "string", true, false, or number.
+pub fn text_mode_value_matches(content: &str, key: &str) -> Result<Vec<std::ops::Range<usize>>> {
+	let re = text_mode_value_regex(key)?;
+	Ok(re.find_iter(content).map(|m| m.range()).collect())
+}
+
 pub fn update_json_value_text_mode(content: &str, prop_path: &[&str], value: &Value) -> Result<String> {
 	let Some(key) = prop_path.last() else {
 		return Err(crate::Error::custom("prop_path cannot be empty"));
 	};
 
-	// This regex matches "key": value
-	// value can be "string", true, false, or number
-	let pattern = format!(r#""{}":\s*("[^"]*"|true|false|[0-9.]+)"#, regex::escape(key));
-	let re = regex::Regex::new(&pattern).map_err(|_| crate::Error::custom("Failed to compile regex"))?;
+	let re = text_mode_value_regex(key)?;
 
 	let matches: Vec<_> = re.find_iter(content).collect();
 	if matches.len() > 1 {
@@ -125,6 +210,13 @@ pub fn toggle_bool_text_mode(content: &str, prop_path: &[&str]) -> Result<String
 
 // region:    --- Support
 
+/// This regex matches "key": value
+/// value can be "string", true, false, or number
+fn text_mode_value_regex(key: &str) -> Result<regex::Regex> {
+	let pattern = format!(r#""{}":\s*("[^"]*"|true|false|[0-9.]+)"#, regex::escape(key));
+	regex::Regex::new(&pattern).map_err(|_| crate::Error::custom("Failed to compile regex"))
+}
+
 pub fn truncate_with_ellipsis<'a>(content: &'a str, max_chars: usize, ellipsis: &str) -> Cow<'a, str> {
 	let s_len = content.chars().count();
 	let ellipsis_len = ellipsis.chars().count();
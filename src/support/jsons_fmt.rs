@@ -0,0 +1,471 @@
+use crate::Result;
+use crate::support::jsons;
+use jsonc_parser::cst::{CstContainerNode, CstLeafNode, CstNode, ObjectPropName};
+
+// region:    --- Types
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SortKeys {
+	None,
+	/// Sort the keys of the root object only
+	Top,
+	/// Sort the keys of all the objects
+	Recursive,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Indent {
+	Tab,
+	Spaces(usize),
+}
+
+impl Indent {
+	/// Parse `tab` (or `tabs`) or a number of spaces.
+	pub fn from_arg(arg: &str) -> Result<Self> {
+		match arg {
+			"tab" | "tabs" => Ok(Indent::Tab),
+			_ => match arg.parse::<usize>() {
+				Ok(n) if n > 0 => Ok(Indent::Spaces(n)),
+				_ => Err(format!("Invalid indent '{arg}' (use 'tab' or a number of spaces)").into()),
+			},
+		}
+	}
+
+	/// The indent of the first indented line of the content (default 2 spaces).
+	pub fn detect(content: &str) -> Self {
+		for line in content.lines() {
+			if line.starts_with('\t') {
+				return Indent::Tab;
+			}
+			let spaces = line.len() - line.trim_start_matches(' ').len();
+			if spaces > 0 && !line.trim().is_empty() {
+				return Indent::Spaces(spaces);
+			}
+		}
+		Indent::Spaces(2)
+	}
+
+	fn unit(&self) -> String {
+		match self {
+			Indent::Tab => "\t".to_string(),
+			Indent::Spaces(n) => " ".repeat(*n),
+		}
+	}
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum TrailingCommas {
+	/// Keep a trailing comma where the object/array had one
+	Keep,
+	Remove,
+}
+
+#[derive(Debug, Clone, Copy)]
+pub struct FmtOptions {
+	pub sort: SortKeys,
+	pub indent: Indent,
+	pub trailing_commas: TrailingCommas,
+}
+
+// endregion: --- Types
+
+/// Format a jsonc content (e.g., Zed settings.json), built on the jsons CST.
+/// - Comments stay attached to the key (or array item) they precede, or to the line they end.
+/// - Single line objects/arrays without comments stay on one line.
+/// - Blank lines between keys are kept (unless the keys are sorted).
+pub fn format_jsonc(content: &str, options: &FmtOptions) -> Result<String> {
+	let root = jsons::parse_jsonc_to_cst(content)?;
+
+	let mut leading = Vec::new();
+	let mut trailing = Vec::new();
+	let mut value = None;
+	for child in root.children() {
+		match child {
+			CstNode::Leaf(CstLeafNode::Comment(comment)) => {
+				if value.is_none() {
+					leading.push(comment.to_string());
+				} else {
+					trailing.push(comment.to_string());
+				}
+			}
+			CstNode::Leaf(CstLeafNode::Whitespace(_) | CstLeafNode::Newline(_)) => (),
+			node => value = Some(node),
+		}
+	}
+
+	let Some(value) = value else {
+		return Ok(content.to_string());
+	};
+
+	let printer = Printer {
+		options,
+		indent_unit: options.indent.unit(),
+	};
+
+	let mut out = String::new();
+	for comment in leading {
+		out.push_str(&comment);
+		out.push('\n');
+	}
+	printer.write_value(&mut out, &FmtValue::from_node(&value), 0);
+	out.push('\n');
+	for comment in trailing {
+		out.push_str(&comment);
+		out.push('\n');
+	}
+
+	Ok(out)
+}
+
+// region:    --- Fmt Model
+
+/// A value with its comments (extracted from the CST).
+enum FmtValue {
+	Object(FmtContainer),
+	Array(FmtContainer),
+	Scalar(String),
+}
+
+struct FmtContainer {
+	items: Vec<FmtItem>,
+	/// Comments after the last item
+	end_comments: Vec<String>,
+	trailing_comma: bool,
+	/// Single line in the source, without comments
+	inline: bool,
+}
+
+struct FmtItem {
+	/// Raw name (with quotes) for object props
+	name: Option<String>,
+	sort_name: String,
+	value: FmtValue,
+	leading_comments: Vec<String>,
+	/// Comments at the end of the item line
+	line_comments: Vec<String>,
+	blank_line_before: bool,
+}
+
+impl FmtValue {
+	fn from_node(node: &CstNode) -> Self {
+		match node {
+			CstNode::Container(CstContainerNode::Object(obj)) => FmtValue::Object(FmtContainer::from_children(
+				obj.children(),
+				obj.to_string().contains('\n'),
+			)),
+			CstNode::Container(CstContainerNode::Array(arr)) => FmtValue::Array(FmtContainer::from_children(
+				arr.children(),
+				arr.to_string().contains('\n'),
+			)),
+			other => FmtValue::Scalar(other.to_string()),
+		}
+	}
+
+	/// Multiline or with comments
+	fn is_block(&self) -> bool {
+		match self {
+			FmtValue::Object(c) | FmtValue::Array(c) => !c.inline,
+			FmtValue::Scalar(_) => false,
+		}
+	}
+}
+
+impl FmtContainer {
+	fn from_children(children: Vec<CstNode>, multiline_source: bool) -> Self {
+		let mut items: Vec<FmtItem> = Vec::new();
+		let mut pending_comments: Vec<String> = Vec::new();
+		let mut pending_blank = false;
+		let mut newlines = 0;
+		// true while on the line of the last item (for its end of line comments)
+		let mut on_item_line = false;
+		let mut last_item_has_comma = false;
+		let mut has_comments = false;
+
+		for child in children {
+			match child {
+				CstNode::Leaf(CstLeafNode::Token(token)) => {
+					if token.value() == ',' {
+						last_item_has_comma = true;
+					}
+				}
+				CstNode::Leaf(CstLeafNode::Whitespace(_)) => (),
+				CstNode::Leaf(CstLeafNode::Newline(_)) => {
+					newlines += 1;
+					on_item_line = false;
+				}
+				CstNode::Leaf(CstLeafNode::Comment(comment)) => {
+					has_comments = true;
+					match items.last_mut() {
+						Some(item) if on_item_line => item.line_comments.push(comment.to_string()),
+						_ => {
+							if pending_comments.is_empty() {
+								pending_blank = newlines >= 2;
+							}
+							pending_comments.push(comment.to_string());
+						}
+					}
+					newlines = 0;
+				}
+				node => {
+					let blank_line_before = if pending_comments.is_empty() {
+						newlines >= 2
+					} else {
+						pending_blank
+					};
+					let mut item = FmtItem::from_node(&node);
+					has_comments |= !item.leading_comments.is_empty() || item.value.is_block();
+					pending_comments.append(&mut item.leading_comments);
+					item.leading_comments = std::mem::take(&mut pending_comments);
+					item.blank_line_before = blank_line_before && !items.is_empty();
+					items.push(item);
+
+					newlines = 0;
+					on_item_line = true;
+					last_item_has_comma = false;
+				}
+			}
+		}
+
+		FmtContainer {
+			inline: !multiline_source && !has_comments,
+			trailing_comma: last_item_has_comma && !items.is_empty(),
+			items,
+			end_comments: pending_comments,
+		}
+	}
+}
+
+impl FmtItem {
+	fn from_node(node: &CstNode) -> Self {
+		let CstNode::Container(CstContainerNode::ObjectProp(prop)) = node else {
+			return FmtItem {
+				name: None,
+				sort_name: String::new(),
+				value: FmtValue::from_node(node),
+				leading_comments: Vec::new(),
+				line_comments: Vec::new(),
+				blank_line_before: false,
+			};
+		};
+
+		let (name, sort_name) = match prop.name() {
+			Some(ObjectPropName::String(lit)) => (lit.to_string(), lit.decoded_value().unwrap_or_default()),
+			Some(ObjectPropName::Word(lit)) => (lit.to_string(), lit.to_string()),
+			None => (String::new(), String::new()),
+		};
+
+		// comments inside the prop (e.g., between the name and the value) go before the prop
+		let leading_comments = prop
+			.children()
+			.into_iter()
+			.filter_map(|c| match c {
+				CstNode::Leaf(CstLeafNode::Comment(comment)) => Some(comment.to_string()),
+				_ => None,
+			})
+			.collect();
+
+		let value = match prop.value() {
+			Some(value) => FmtValue::from_node(&value),
+			None => FmtValue::Scalar("null".to_string()),
+		};
+
+		FmtItem {
+			name: Some(name),
+			sort_name,
+			value,
+			leading_comments,
+			line_comments: Vec::new(),
+			blank_line_before: false,
+		}
+	}
+}
+
+// endregion: --- Fmt Model
+
+// region:    --- Printer
+
+struct Printer<'a> {
+	options: &'a FmtOptions,
+	indent_unit: String,
+}
+
+impl Printer<'_> {
+	fn write_value(&self, out: &mut String, value: &FmtValue, depth: usize) {
+		match value {
+			FmtValue::Scalar(raw) => out.push_str(raw),
+			FmtValue::Object(container) => {
+				let sort = match self.options.sort {
+					SortKeys::None => false,
+					SortKeys::Top => depth == 0,
+					SortKeys::Recursive => true,
+				};
+				self.write_container(out, container, ('{', '}'), sort, depth)
+			}
+			FmtValue::Array(container) => self.write_container(out, container, ('[', ']'), false, depth),
+		}
+	}
+
+	fn write_container(
+		&self,
+		out: &mut String,
+		container: &FmtContainer,
+		(open, close): (char, char),
+		sort: bool,
+		depth: usize,
+	) {
+		let mut items: Vec<&FmtItem> = container.items.iter().collect();
+		if sort {
+			items.sort_by(|a, b| a.sort_name.cmp(&b.sort_name));
+		}
+
+		// -- Empty or inline
+		if items.is_empty() && container.end_comments.is_empty() {
+			out.push(open);
+			out.push(close);
+			return;
+		}
+		if container.inline {
+			out.push(open);
+			let pad = if open == '{' { " " } else { "" };
+			out.push_str(pad);
+			for (idx, item) in items.iter().enumerate() {
+				if idx > 0 {
+					out.push_str(", ");
+				}
+				self.write_item_value(out, item, depth);
+			}
+			if self.keep_trailing_comma(container) {
+				out.push(',');
+			}
+			out.push_str(pad);
+			out.push(close);
+			return;
+		}
+
+		// -- Multiline
+		let inner_indent = self.indent_unit.repeat(depth + 1);
+		out.push(open);
+		out.push('\n');
+		let last_idx = items.len().saturating_sub(1);
+		for (idx, item) in items.iter().enumerate() {
+			if item.blank_line_before && !sort {
+				out.push('\n');
+			}
+			for comment in item.leading_comments.iter() {
+				out.push_str(&inner_indent);
+				out.push_str(comment);
+				out.push('\n');
+			}
+			out.push_str(&inner_indent);
+			self.write_item_value(out, item, depth + 1);
+			if idx < last_idx || self.keep_trailing_comma(container) {
+				out.push(',');
+			}
+			for comment in item.line_comments.iter() {
+				out.push(' ');
+				out.push_str(comment);
+			}
+			out.push('\n');
+		}
+		for comment in container.end_comments.iter() {
+			out.push_str(&inner_indent);
+			out.push_str(comment);
+			out.push('\n');
+		}
+		out.push_str(&self.indent_unit.repeat(depth));
+		out.push(close);
+	}
+
+	fn keep_trailing_comma(&self, container: &FmtContainer) -> bool {
+		container.trailing_comma && self.options.trailing_commas == TrailingCommas::Keep
+	}
+
+	fn write_item_value(&self, out: &mut String, item: &FmtItem, depth: usize) {
+		if let Some(name) = item.name.as_deref() {
+			out.push_str(name);
+			out.push_str(": ");
+		}
+		self.write_value(out, &item.value, depth);
+	}
+}
+
+// endregion: --- Printer
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_support_jsons_fmt_sort_keep_comments() -> Result<()> {
+		// -- Setup & Fixtures
+		let content = r#"// Zed settings
+{
+    // the theme
+    "theme": "One Dark",
+    "languages": {
+        "Rust": { "tab_size": 2 },
+        /* python */
+        "Python": {
+            "tab_size": 4, // pep8
+        },
+    },
+
+    "buffer_font_size": 15, // small
+}
+"#;
+		let options = FmtOptions {
+			sort: SortKeys::Recursive,
+			indent: Indent::Tab,
+			trailing_commas: TrailingCommas::Remove,
+		};
+
+		// -- Exec
+		let formatted = format_jsonc(content, &options)?;
+
+		// -- Check
+		let expected = "// Zed settings
+{
+	\"buffer_font_size\": 15, // small
+	\"languages\": {
+		/* python */
+		\"Python\": {
+			\"tab_size\": 4 // pep8
+		},
+		\"Rust\": { \"tab_size\": 2 }
+	},
+	// the theme
+	\"theme\": \"One Dark\"
+}
+";
+		assert_eq!(formatted, expected);
+		// idempotent
+		assert_eq!(format_jsonc(&formatted, &options)?, formatted);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_support_jsons_fmt_keep_order_and_trailing_commas() -> Result<()> {
+		// -- Setup & Fixtures
+		let content = "{\n  \"b\": [1, 2],\n\n  \"a\": true,\n  \"c\": { \"d\": [3, 4,], \"e\": 5, },\n}\n";
+		let options = FmtOptions {
+			sort: SortKeys::None,
+			indent: Indent::detect(content),
+			trailing_commas: TrailingCommas::Keep,
+		};
+
+		// -- Exec
+		let formatted = format_jsonc(content, &options)?;
+
+		// -- Check
+		assert_eq!(formatted, content);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,16 @@
+/// Render a Markdown content to HTML (tables, footnotes, strikethrough, task lists, smart punctuation).
+pub fn markdown_to_html(content: &str) -> String {
+	let mut options = pulldown_cmark::Options::empty();
+	options.insert(pulldown_cmark::Options::ENABLE_TABLES);
+	options.insert(pulldown_cmark::Options::ENABLE_FOOTNOTES);
+	options.insert(pulldown_cmark::Options::ENABLE_STRIKETHROUGH);
+	options.insert(pulldown_cmark::Options::ENABLE_TASKLISTS);
+	options.insert(pulldown_cmark::Options::ENABLE_SMART_PUNCTUATION);
+
+	let parser = pulldown_cmark::Parser::new_ext(content, options);
+
+	let mut html_output = String::new();
+	pulldown_cmark::html::push_html(&mut html_output, parser);
+
+	html_output
+}
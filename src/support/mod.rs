@@ -2,12 +2,23 @@
 
 pub mod alacritty;
 pub mod clipboard;
+pub mod config;
+pub mod diffs;
+pub mod files;
+pub mod hash;
+pub mod history;
+pub mod images;
 pub mod jsons;
+pub mod jsons_fmt;
 pub mod mac;
+pub mod markdown;
 pub mod os;
 pub mod proc;
+pub mod text;
+pub mod theme_export;
 pub mod tmux;
 pub mod tomls;
+pub mod writer;
 pub mod zed;
 
 // endregion: --- Modules
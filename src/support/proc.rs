@@ -1,5 +1,6 @@
 use crate::Result;
-use std::process::Command;
+use std::io::Write as _;
+use std::process::{Command, Stdio};
 
 pub fn run_proc(cmd: &str, args: &[&str]) -> Result<String> {
 	let output = Command::new(cmd).args(args).output()?;
@@ -12,6 +13,48 @@ pub fn run_proc(cmd: &str, args: &[&str]) -> Result<String> {
 	Ok(String::from_utf8_lossy(&output.stdout).to_string())
 }
 
+/// Like `run_proc`, but returns the raw stdout (e.g., for binary content).
+pub fn run_proc_bytes(cmd: &str, args: &[&str]) -> Result<Vec<u8>> {
+	let output = Command::new(cmd).args(args).output()?;
+
+	if !output.status.success() {
+		let err_msg = String::from_utf8_lossy(&output.stderr).to_string();
+		return Err(crate::Error::custom(format!("Command '{cmd}' failed: {err_msg}")));
+	}
+
+	Ok(output.stdout)
+}
+
+/// Run a command with `input` as stdin.
+/// Note: stdout is not captured, since some commands (e.g., `xclip -i`, `wl-copy`) keep it open in the background.
+pub fn run_proc_with_stdin(cmd: &str, args: &[&str], input: &[u8]) -> Result<()> {
+	let mut child = Command::new(cmd)
+		.args(args)
+		.stdin(Stdio::piped())
+		.stdout(Stdio::null())
+		.stderr(Stdio::null())
+		.spawn()?;
+
+	if let Some(mut stdin) = child.stdin.take() {
+		stdin.write_all(input)?;
+	}
+
+	let status = child.wait()?;
+	if !status.success() {
+		return Err(crate::Error::custom(format!("Command '{cmd}' failed ({status})")));
+	}
+
+	Ok(())
+}
+
+/// The command is an executable file in one of the PATH directories.
+pub fn command_exists(cmd: &str) -> bool {
+	let Some(path) = std::env::var_os("PATH") else {
+		return false;
+	};
+	std::env::split_paths(&path).any(|dir| dir.join(cmd).is_file())
+}
+
 // Might be needed later (was for the term new/pos which is now hammerspoon)
 #[allow(unused)]
 pub fn is_proc_running(name: &str) -> bool {
@@ -0,0 +1,45 @@
+/// Lowercase slug with `sep` between the alphanumeric words (e.g., "One Dark Pro" => "one-dark-pro").
+pub fn slugify(text: &str, sep: char) -> String {
+	let mut slug = String::new();
+	for c in text.chars() {
+		if c.is_alphanumeric() {
+			slug.extend(c.to_lowercase());
+		} else if !slug.is_empty() && !slug.ends_with(sep) {
+			slug.push(sep);
+		}
+	}
+	slug.trim_end_matches(sep).to_string()
+}
+
+/// The 1-based (line, column) of a byte offset in a text (column in chars).
+pub fn line_col(text: &str, offset: usize) -> (usize, usize) {
+	let before = &text[..offset.min(text.len())];
+	let line = before.matches('\n').count() + 1;
+	let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+	let column = before[line_start..].chars().count() + 1;
+	(line, column)
+}
+
+/// Decodes the `%XX` escapes (e.g., "my%20notes" => "my notes"), leaving the invalid ones as is.
+pub fn percent_decode(text: &str) -> String {
+	let bytes = text.as_bytes();
+	let mut decoded = Vec::with_capacity(bytes.len());
+	let mut idx = 0;
+	while idx < bytes.len() {
+		let hex = (bytes[idx] == b'%')
+			.then(|| text.get(idx + 1..idx + 3))
+			.flatten()
+			.and_then(|hex| u8::from_str_radix(hex, 16).ok());
+		match hex {
+			Some(byte) => {
+				decoded.push(byte);
+				idx += 3;
+			}
+			None => {
+				decoded.push(bytes[idx]);
+				idx += 1;
+			}
+		}
+	}
+	String::from_utf8_lossy(&decoded).to_string()
+}
@@ -0,0 +1,159 @@
+use crate::Result;
+use crate::support::zed::ZedThemeDef;
+
+const ANSI_NAMES: [&str; 8] = ["black", "red", "green", "yellow", "blue", "magenta", "cyan", "white"];
+
+// region:    --- Types
+
+/// The terminal colors of a Zed theme (from the `terminal.*` style keys).
+#[derive(Debug)]
+pub struct TerminalColors {
+	pub theme_name: String,
+	pub background: String,
+	pub foreground: String,
+	/// (ansi name, color) for normal, bright, and dim (only the ones defined)
+	pub normal: Vec<(&'static str, String)>,
+	pub bright: Vec<(&'static str, String)>,
+	pub dim: Vec<(&'static str, String)>,
+	/// UI colors for the tmux status and pane borders
+	pub status_background: String,
+	pub status_foreground: String,
+	pub border: String,
+	pub border_active: String,
+}
+
+impl TerminalColors {
+	pub fn from_zed_theme(theme: &ZedThemeDef) -> Result<Self> {
+		let required = |key: &str| -> Result<String> {
+			theme
+				.style_color(key)
+				.ok_or_else(|| format!("Zed theme '{}' has no '{key}' color", theme.name).into())
+		};
+		let ansi = |prefix: &str| -> Vec<(&'static str, String)> {
+			ANSI_NAMES
+				.iter()
+				.filter_map(|name| Some((*name, theme.style_color(&format!("terminal.ansi.{prefix}{name}"))?)))
+				.collect()
+		};
+
+		let background = required("terminal.background")?;
+		let foreground = required("terminal.foreground")?;
+		let normal = ansi("");
+		if normal.is_empty() {
+			return Err(format!("Zed theme '{}' has no 'terminal.ansi.*' colors", theme.name).into());
+		}
+
+		let status_background = theme.style_color("status_bar.background").unwrap_or_else(|| background.clone());
+		let status_foreground = theme.style_color("text").unwrap_or_else(|| foreground.clone());
+		let border = theme.style_color("border").unwrap_or_else(|| foreground.clone());
+		let border_active = theme
+			.style_color("border.focused")
+			.or_else(|| theme.style_color("text.accent"))
+			.unwrap_or_else(|| foreground.clone());
+
+		Ok(Self {
+			theme_name: theme.name.clone(),
+			background,
+			foreground,
+			normal,
+			bright: ansi("bright_"),
+			dim: ansi("dim_"),
+			status_background,
+			status_foreground,
+			border,
+			border_active,
+		})
+	}
+
+	/// The Alacritty `[colors]` TOML (to be imported from alacritty.toml).
+	pub fn to_alacritty_toml(&self) -> String {
+		let mut out = format!("# Alacritty colors generated from Zed theme '{}' (jc-zed-tasks)\n", self.theme_name);
+
+		out.push_str("\n[colors.primary]\n");
+		out.push_str(&format!("background = \"{}\"\n", self.background));
+		out.push_str(&format!("foreground = \"{}\"\n", self.foreground));
+
+		for (section, colors) in [("normal", &self.normal), ("bright", &self.bright), ("dim", &self.dim)] {
+			if colors.is_empty() {
+				continue;
+			}
+			out.push_str(&format!("\n[colors.{section}]\n"));
+			for (name, color) in colors.iter() {
+				out.push_str(&format!("{name} = \"{color}\"\n"));
+			}
+		}
+
+		out
+	}
+
+	/// The tmux status and pane border snippet (to be sourced from tmux.conf).
+	pub fn to_tmux_conf(&self) -> String {
+		format!(
+			"# tmux colors generated from Zed theme '{}' (jc-zed-tasks)\n\
+			 set -g status-style \"bg={},fg={}\"\n\
+			 set -g message-style \"bg={},fg={}\"\n\
+			 set -g pane-border-style \"fg={}\"\n\
+			 set -g pane-active-border-style \"fg={}\"\n",
+			self.theme_name,
+			self.status_background,
+			self.status_foreground,
+			self.status_background,
+			self.status_foreground,
+			self.border,
+			self.border_active,
+		)
+	}
+}
+
+// endregion: --- Types
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+	use crate::support::zed::ZedThemeFamily;
+
+	#[test]
+	fn test_support_theme_export_alacritty_and_tmux() -> Result<()> {
+		// -- Setup & Fixtures
+		let family: ZedThemeFamily = serde_json::from_str(
+			r##"{
+				"name": "Test",
+				"themes": [{
+					"name": "Test Dark",
+					"appearance": "dark",
+					"style": {
+						"border": "#333333ff",
+						"terminal.background": "#101010ff",
+						"terminal.foreground": "#eeeeee",
+						"terminal.ansi.red": "#ff0000ff",
+						"terminal.ansi.bright_red": "#ff5555ff",
+						"terminal.ansi.green": "#0f0",
+						"terminal.ansi.blue": "#08fc"
+					}
+				}]
+			}"##,
+		)?;
+
+		// -- Exec
+		let colors = TerminalColors::from_zed_theme(&family.themes[0])?;
+		let alacritty_toml = colors.to_alacritty_toml();
+		let tmux_conf = colors.to_tmux_conf();
+
+		// -- Check
+		let value: toml::Value = toml::from_str(&alacritty_toml)?;
+		assert_eq!(value["colors"]["primary"]["background"].as_str(), Some("#101010"));
+		assert_eq!(value["colors"]["normal"]["red"].as_str(), Some("#ff0000"));
+		assert_eq!(value["colors"]["bright"]["red"].as_str(), Some("#ff5555"));
+		assert_eq!(value["colors"]["normal"]["green"].as_str(), Some("#00ff00"));
+		assert_eq!(value["colors"]["normal"]["blue"].as_str(), Some("#0088ff"));
+		assert!(tmux_conf.contains(r##"set -g pane-border-style "fg=#333333""##));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
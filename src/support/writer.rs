@@ -0,0 +1,135 @@
+use crate::Result;
+use crate::support::{diffs, files};
+use simple_fs::SPath;
+use std::cell::Cell;
+use std::fs;
+use std::io::IsTerminal as _;
+
+thread_local! {
+	/// When set (global `--dry-run`), the file writes and side effects going through this module
+	/// print what would change instead of doing it.
+	/// Note: Per thread (the commands run on the main thread), so the tests setting it do not affect the others.
+	static DRY_RUN: Cell<bool> = const { Cell::new(false) };
+}
+
+/// Set the global dry-run mode (from the `--dry-run` flag).
+pub fn set_dry_run(dry_run: bool) {
+	DRY_RUN.set(dry_run);
+}
+
+pub fn is_dry_run() -> bool {
+	DRY_RUN.get()
+}
+
+/// Write the file (atomically), or in dry-run mode, print the diff (or "would create") instead.
+pub fn write_file(path: &SPath, content: impl AsRef<[u8]>) -> Result<()> {
+	let content = content.as_ref();
+	if is_dry_run() {
+		print_write_preview(path, content)?;
+		return Ok(());
+	}
+	files::write_atomic(path, content)
+}
+
+/// Remove the file, or in dry-run mode, print "would remove" instead.
+pub fn remove_file(path: &SPath) -> Result<()> {
+	if is_dry_run() {
+		println!("Would remove {path}");
+		return Ok(());
+	}
+	fs::remove_file(path.std_path())?;
+	Ok(())
+}
+
+/// Returns true if the side effect (e.g., tmux send-keys, window move) should be done.
+/// In dry-run mode, prints "Would <description>" and returns false.
+pub fn side_effect(description: &str) -> bool {
+	if is_dry_run() {
+		println!("Would {description}");
+		false
+	} else {
+		true
+	}
+}
+
+/// Print what a write would change: the unified diff for text files (colored on a terminal),
+/// a "would create" notice for new files, and the sizes for binary files.
+pub fn print_write_preview(path: &SPath, content: &[u8]) -> Result<()> {
+	let old = if path.exists() {
+		Some(fs::read(path.std_path())?)
+	} else {
+		None
+	};
+
+	let (old_text, new_text) = match (old.as_deref().map(std::str::from_utf8), std::str::from_utf8(content)) {
+		(Some(Ok(old)), Ok(new)) => (Some(old), new),
+		(None, Ok(new)) => (None, new),
+		// -- Binary (e.g., images)
+		_ => {
+			match old {
+				Some(old) => println!(
+					"Would overwrite {path} ({} bytes -> {} bytes)",
+					old.len(),
+					content.len()
+				),
+				None => println!("Would create {path} ({} bytes)", content.len()),
+			}
+			return Ok(());
+		}
+	};
+
+	if old_text.is_none() {
+		println!("Would create {path}");
+	}
+	let diff = diffs::unified_diff(old_text.unwrap_or_default(), new_text, path.as_str(), path.as_str());
+	if diff.is_empty() {
+		println!("No changes to {path}");
+	} else if std::io::stdout().is_terminal() && std::env::var_os("NO_COLOR").is_none() {
+		print!("{}", diffs::colorize_unified_diff(&diff));
+	} else {
+		print!("{diff}");
+	}
+
+	Ok(())
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_support_writer_dry_run_leaves_files_untouched() -> Result<()> {
+		// -- Setup & Fixtures
+		let dir = std::env::temp_dir().join(format!("jc-zed-tasks-writer-{}", std::process::id()));
+		fs::create_dir_all(&dir)?;
+		let dir = SPath::from_std_path(&dir)?;
+		let file = dir.join("settings.json");
+		let new_file = dir.join("new.json");
+		fs::write(file.std_path(), "before")?;
+
+		// -- Exec
+		set_dry_run(true);
+		let write_res = write_file(&file, "after");
+		let create_res = write_file(&new_file, "new");
+		let remove_res = remove_file(&file);
+		set_dry_run(false);
+
+		// -- Check
+		write_res?;
+		create_res?;
+		remove_res?;
+		assert_eq!(fs::read_to_string(file.std_path())?, "before");
+		assert!(!new_file.exists());
+
+		// -- Cleanup
+		fs::remove_dir_all(dir.std_path())?;
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
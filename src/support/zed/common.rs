@@ -0,0 +1,117 @@
+use crate::Result;
+use crate::support::zed::ZedTaskEnv;
+use crate::support::{config, jsons, writer};
+use simple_fs::SPath;
+use std::fs::{self, OpenOptions};
+use std::time::{Duration, SystemTime};
+
+/// Returns the Zed user config directory (`~/.config/zed`).
+pub fn config_dir() -> Result<SPath> {
+	let home = home::home_dir().ok_or("Could not find home directory")?;
+	Ok(SPath::from_std_path(home)?.join(".config/zed"))
+}
+
+/// Returns the path of the Zed user tasks file (`~/.config/zed/tasks.json`).
+pub fn tasks_json_path() -> Result<SPath> {
+	Ok(config_dir()?.join("tasks.json"))
+}
+
+/// Touches the Zed tasks.json file(s) to trigger a reload if they exist.
+/// NOTE: Needed because of a Zed bug (2026-01-09) that it does not refresh the current file in the environment variable.
+///       The work around is to touch the zed tasks.json, and then, the current file
+///       Now, since this binary is called after, we just touch_tasks_json for helping the next call. Not bullet proof, but should help.
+///
+/// Targets are configured in the `touch_tasks_json` config section
+/// (global `~/.config/zed/tasks.json` by default, and optionally the project `<worktree>/.zed/tasks.json`).
+pub fn touch_tasks_json(zed_env: &ZedTaskEnv) -> Result<()> {
+	// Note: A bad config.json must not fail the command calling this (it is only a workaround)
+	let touch_config = match config::load_config() {
+		Ok(app_config) => app_config.touch_tasks_json,
+		Err(err) => {
+			eprintln!("Warning: Cannot load the config, using the default tasks.json touch targets. Cause: {err}");
+			Default::default()
+		}
+	};
+
+	let mut tasks_paths = Vec::new();
+	if touch_config.global {
+		tasks_paths.push(tasks_json_path()?);
+	}
+	if touch_config.project
+		&& let Some(worktree_root) = zed_env.worktree_root.as_deref()
+	{
+		tasks_paths.push(SPath::new(worktree_root).join(".zed/tasks.json"));
+	}
+
+	for tasks_path in tasks_paths {
+		if tasks_path.exists() && writer::side_effect(&format!("touch {tasks_path}")) {
+			touch_file(&tasks_path)?;
+		}
+	}
+
+	Ok(())
+}
+
+/// Set the modified time of an existing file to now (without changing its content),
+/// and verify it by reading back the metadata.
+pub fn touch_file(path: &SPath) -> Result<()> {
+	let now = SystemTime::now();
+
+	OpenOptions::new().write(true).open(path)?.set_modified(now)?;
+
+	// -- Verify (allow for the coarse mtime granularity of some filesystems, e.g., 2s for FAT)
+	let modified = fs::metadata(path)?.modified()?;
+	let delta = match now.duration_since(modified) {
+		Ok(delta) => delta,
+		Err(err) => err.duration(),
+	};
+	if delta > Duration::from_secs(2) {
+		return Err(format!("Could not update the modified time of {path}").into());
+	}
+
+	Ok(())
+}
+
+pub fn load_settings() -> Result<serde_json::Value> {
+	let settings_path = config_dir()?.join("settings.json");
+
+	let value = jsons::load_jsons_to_serde_value(&settings_path)?
+		.ok_or_else(|| format!("Zed settings file is empty: {settings_path}"))?;
+
+	Ok(value)
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+	use std::time::UNIX_EPOCH;
+
+	#[test]
+	fn test_support_zed_common_touch_file_simple() -> Result<()> {
+		// -- Setup & Fixtures
+		let path = std::env::temp_dir().join(format!("jc-zed-tasks-touch-{}.json", std::process::id()));
+		fs::write(&path, "[]")?;
+		let old_time = UNIX_EPOCH + Duration::from_secs(1_000_000);
+		OpenOptions::new().write(true).open(&path)?.set_modified(old_time)?;
+		let path = SPath::from_std_path(&path)?;
+
+		// -- Exec
+		touch_file(&path)?;
+
+		// -- Check
+		let modified = fs::metadata(&path)?.modified()?;
+		assert!(modified > old_time);
+		assert_eq!(fs::read_to_string(&path)?, "[]");
+
+		// -- Cleanup
+		fs::remove_file(&path)?;
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,246 @@
+use crate::Result;
+use crate::support::config::KeymapBindingConfig;
+use crate::support::jsons;
+use crate::support::zed::{TASK_CATALOG, ZedTaskDef, config_dir, normalize_key};
+use jsonc_parser::cst::{CstArray, CstInputValue, CstObject};
+use serde_json::{Value, json};
+use simple_fs::SPath;
+
+// region:    --- Types
+
+#[derive(Debug)]
+pub struct KeymapConflict {
+	pub context: Option<String>,
+	pub key: String,
+	/// The existing binding value (as json text)
+	pub existing: String,
+	/// What we wanted to bind (e.g., the task label)
+	pub wanted: String,
+}
+
+/// A key binding to merge into the keymap.json.
+#[derive(Debug)]
+pub struct KeymapActionBinding {
+	pub context: Option<String>,
+	pub key: String,
+	/// The action value (e.g., `"editor::Format"` or `["task::Spawn", {...}]`)
+	pub action: Value,
+	/// The display name of the action (for the report)
+	pub label: String,
+}
+
+#[derive(Debug, Default)]
+pub struct KeymapMergeReport {
+	pub added: Vec<String>,
+	pub unchanged: Vec<String>,
+	pub conflicts: Vec<KeymapConflict>,
+}
+
+impl KeymapMergeReport {
+	pub fn has_changes(&self) -> bool {
+		!self.added.is_empty()
+	}
+}
+
+// endregion: --- Types
+
+/// Returns the path of the Zed user keymap file (`~/.config/zed/keymap.json`).
+pub fn keymap_json_path() -> Result<SPath> {
+	Ok(config_dir()?.join("keymap.json"))
+}
+
+/// The `task::Spawn` binding value for a task.
+pub fn task_spawn_action(task: &ZedTaskDef) -> Value {
+	json!(["task::Spawn", { "task_name": task.label() }])
+}
+
+/// Merge the `task::Spawn` bindings into the keymap.json content (see `merge_keymap_bindings`).
+pub fn install_keymap_bindings(content: &str, bindings: &[KeymapBindingConfig]) -> Result<(String, KeymapMergeReport)> {
+	let mut action_bindings = Vec::new();
+	for binding in bindings {
+		let task = TASK_CATALOG
+			.iter()
+			.find(|t| t.name == binding.task)
+			.ok_or_else(|| format!("Unknown task '{}' in keymap config", binding.task))?;
+		action_bindings.push(KeymapActionBinding {
+			context: binding.context.clone(),
+			key: binding.key.clone(),
+			action: task_spawn_action(task),
+			label: task.label(),
+		});
+	}
+
+	merge_keymap_bindings(content, &action_bindings)
+}
+
+/// Merge the bindings into the keymap.json content (array of `{context, bindings}` blocks).
+/// - A key already bound to something else in the same context is reported as a conflict (and left untouched).
+/// - A key already bound to the same action is left as is (no duplicate on re-run).
+///
+/// Keys are compared normalized (e.g., `cmd-alt-h` and `alt-cmd-h`), and the last binding wins (like Zed).
+pub fn merge_keymap_bindings(content: &str, bindings: &[KeymapActionBinding]) -> Result<(String, KeymapMergeReport)> {
+	let root = jsons::parse_jsonc_to_cst(content)?;
+	let blocks = root
+		.array_value_or_create()
+		.ok_or("Zed keymap.json root must be an array")?;
+
+	let mut report = KeymapMergeReport::default();
+
+	for binding in bindings {
+		let action = &binding.action;
+		let context = binding.context.as_deref();
+		let display = format!(
+			"{} ({}) -> {}",
+			binding.key,
+			context.unwrap_or("<no context>"),
+			binding.label
+		);
+
+		// -- Check existing bindings in the same context (the last one applies)
+		let context_blocks = find_context_blocks(&blocks, context);
+		let key = normalize_key(&binding.key);
+		let existing = context_blocks.iter().rev().find_map(|block| {
+			let props = block.object_value("bindings")?.properties();
+			let prop = props.into_iter().rev().find(|prop| {
+				prop.name()
+					.and_then(|name| name.decoded_value().ok())
+					.is_some_and(|name| normalize_key(&name) == key)
+			})?;
+			prop.value()?.to_serde_value()
+		});
+
+		match existing {
+			Some(existing) if &existing == action => report.unchanged.push(display),
+			Some(existing) => report.conflicts.push(KeymapConflict {
+				context: binding.context.clone(),
+				key: binding.key.clone(),
+				existing: existing.to_string(),
+				wanted: binding.label.clone(),
+			}),
+			None => {
+				let block = match context_blocks.into_iter().next() {
+					Some(block) => block,
+					None => append_context_block(&blocks, context)?,
+				};
+				block
+					.object_value_or_set("bindings")
+					.append(&binding.key, jsons::serde_value_to_cst_input(action));
+				report.added.push(display);
+			}
+		}
+	}
+
+	Ok((root.to_string(), report))
+}
+
+// region:    --- Support
+
+/// Returns the blocks matching the context (a `None` context matches the blocks without context).
+fn find_context_blocks(blocks: &CstArray, context: Option<&str>) -> Vec<CstObject> {
+	blocks
+		.elements()
+		.into_iter()
+		.filter_map(|el| el.as_object())
+		.filter(|obj| jsons::cst_object_str_prop(obj, "context").as_deref() == context)
+		.collect()
+}
+
+fn append_context_block(blocks: &CstArray, context: Option<&str>) -> Result<CstObject> {
+	// Note: Built by hand to keep "context" before "bindings".
+	let mut props = Vec::new();
+	if let Some(context) = context {
+		props.push(("context".to_string(), CstInputValue::from(context)));
+	}
+	props.push(("bindings".to_string(), CstInputValue::Object(Vec::new())));
+
+	let node = blocks.append(CstInputValue::Object(props));
+	let block = node.as_object().ok_or("Fail to append keymap context block")?;
+
+	Ok(block)
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_support_zed_keymap_install_conflict_and_rerun() -> Result<()> {
+		// -- Setup & Fixtures
+		let content = r#"[
+  // editor bindings
+  {
+    "context": "Editor",
+    "bindings": {
+      "cmd-alt-p": "editor::Format"
+    }
+  }
+]"#;
+		let bindings = vec![
+			KeymapBindingConfig {
+				context: Some("Editor".to_string()),
+				key: "cmd-alt-h".to_string(),
+				task: "md-to-html".to_string(),
+			},
+			KeymapBindingConfig {
+				context: Some("Editor".to_string()),
+				key: "cmd-alt-p".to_string(),
+				task: "toggle-profile".to_string(),
+			},
+			KeymapBindingConfig {
+				context: Some("Workspace".to_string()),
+				key: "cmd-alt-i".to_string(),
+				task: "save-clipboard-image".to_string(),
+			},
+		];
+
+		// -- Exec
+		let (content_1, report_1) = install_keymap_bindings(content, &bindings)?;
+		let (content_2, report_2) = install_keymap_bindings(&content_1, &bindings)?;
+
+		// -- Check
+		assert_eq!(report_1.added.len(), 2);
+		assert_eq!(report_1.conflicts.len(), 1);
+		assert_eq!(report_1.conflicts[0].key, "cmd-alt-p");
+		assert!(!report_2.has_changes());
+		assert_eq!(report_2.unchanged.len(), 2);
+		assert_eq!(content_1, content_2);
+		assert!(content_1.contains("// editor bindings"));
+		assert!(content_1.contains(r#""context": "Workspace""#));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_support_zed_keymap_merge_normalized_key_last_block() -> Result<()> {
+		// -- Setup & Fixtures
+		let content = r#"[
+  { "context": "Editor", "bindings": { "alt-cmd-h": "editor::Format" } },
+  { "context": "Editor", "bindings": { "Alt-Cmd-H": "editor::ToggleComments" } }
+]"#;
+		let bindings = vec![KeymapActionBinding {
+			context: Some("Editor".to_string()),
+			key: "cmd-alt-h".to_string(),
+			action: json!("workspace::Save"),
+			label: "workspace::Save".to_string(),
+		}];
+
+		// -- Exec
+		let (new_content, report) = merge_keymap_bindings(content, &bindings)?;
+
+		// -- Check
+		assert!(!report.has_changes());
+		assert_eq!(report.conflicts.len(), 1);
+		assert_eq!(report.conflicts[0].existing, r#""editor::ToggleComments""#);
+		assert_eq!(new_content, content);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
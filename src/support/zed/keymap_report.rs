@@ -0,0 +1,493 @@
+use crate::Result;
+use crate::support::{jsons, text};
+use jsonc_parser::ast;
+use serde_json::Value;
+use std::collections::BTreeSet;
+
+/// Zed context parents (a binding in the child context shadows the same key in the parent when the child is focused).
+const CONTEXT_PARENTS: &[(&str, &str)] = &[
+	("Pane", "Workspace"),
+	("Editor", "Pane"),
+	("Terminal", "Pane"),
+	("BufferSearchBar", "Pane"),
+	("ProjectSearchBar", "Pane"),
+	("ProjectPanel", "Workspace"),
+	("OutlinePanel", "Workspace"),
+	("GitPanel", "Workspace"),
+	("AgentPanel", "Workspace"),
+	("CollabPanel", "Workspace"),
+];
+
+// region:    --- Types
+
+/// One key binding of the keymap.json.
+#[derive(Debug)]
+pub struct KeymapBinding {
+	pub context: Option<String>,
+	pub key: String,
+	pub action: Value,
+	/// 1-based
+	pub line: usize,
+	/// 1-based (in chars)
+	pub column: usize,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum KeymapIssueKind {
+	/// Same key in the same context (only the last one is used)
+	Duplicate,
+	/// Same key in overlapping contexts (one wins where both match)
+	Shadow,
+	/// A key that is the start of a key sequence in an overlapping context
+	Prefix,
+}
+
+impl KeymapIssueKind {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			KeymapIssueKind::Duplicate => "duplicate",
+			KeymapIssueKind::Shadow => "shadow",
+			KeymapIssueKind::Prefix => "prefix",
+		}
+	}
+}
+
+#[derive(Debug)]
+pub struct KeymapIssue {
+	pub kind: KeymapIssueKind,
+	/// Index of the binding the issue is reported on (in the parsed bindings)
+	pub binding_idx: usize,
+	pub message: String,
+}
+
+/// The analyzed context predicate of a keymap block (e.g., `Editor && mode == full`).
+#[derive(Debug, PartialEq, Eq)]
+struct ContextInfo {
+	/// The context names (e.g., `Editor`), empty for no context
+	names: BTreeSet<String>,
+	/// The other terms (e.g., `mode == full`)
+	predicates: BTreeSet<String>,
+	/// With `||`, `(`, or `>` (only compared as is)
+	opaque: Option<String>,
+}
+
+// endregion: --- Types
+
+/// Parse the bindings of a keymap.json content (array of `{context, bindings}` blocks).
+pub fn parse_keymap_bindings(content: &str) -> Result<Vec<KeymapBinding>> {
+	let Some(root) = jsons::parse_jsonc_to_ast(content)? else {
+		return Ok(Vec::new());
+	};
+	let ast::Value::Array(blocks) = root else {
+		return Err("Zed keymap.json root must be an array".into());
+	};
+
+	let mut bindings = Vec::new();
+	for block in blocks.elements.iter() {
+		let ast::Value::Object(block) = block else { continue };
+		let context = block
+			.properties
+			.iter()
+			.find(|p| p.name.as_str() == "context")
+			.and_then(|p| match &p.value {
+				ast::Value::StringLit(s) => Some(s.value.to_string()),
+				_ => None,
+			});
+		let Some(ast::Value::Object(block_bindings)) = block
+			.properties
+			.iter()
+			.find(|p| p.name.as_str() == "bindings")
+			.map(|p| &p.value)
+		else {
+			continue;
+		};
+
+		for prop in block_bindings.properties.iter() {
+			let (line, column) = text::line_col(content, prop.range.start);
+			bindings.push(KeymapBinding {
+				context: context.clone(),
+				key: prop.name.as_str().to_string(),
+				action: Value::from(prop.value.clone()),
+				line,
+				column,
+			});
+		}
+	}
+
+	Ok(bindings)
+}
+
+/// The bindings still active: without the unbindings (`null` action), and without the earlier bindings
+/// of the same key they cancel (in the same or a containing context).
+pub fn drop_unbound_bindings(bindings: Vec<KeymapBinding>) -> Vec<KeymapBinding> {
+	let contexts: Vec<ContextInfo> = bindings
+		.iter()
+		.map(|b| ContextInfo::parse(b.context.as_deref()))
+		.collect();
+	let keys: Vec<String> = bindings.iter().map(|b| normalize_key(&b.key)).collect();
+
+	let is_cancelled = |idx: usize| {
+		bindings.iter().enumerate().skip(idx + 1).any(|(later_idx, later)| {
+			later.action.is_null() && keys[later_idx] == keys[idx] && contexts[later_idx].contains(&contexts[idx])
+		})
+	};
+	let active: Vec<bool> = (0..bindings.len())
+		.map(|idx| !bindings[idx].action.is_null() && !is_cancelled(idx))
+		.collect();
+
+	bindings
+		.into_iter()
+		.zip(active)
+		.filter_map(|(binding, active)| active.then_some(binding))
+		.collect()
+}
+
+/// Find the duplicate, shadowing, and prefix bindings (sorted by position).
+/// Expects the active bindings (see `drop_unbound_bindings`).
+pub fn analyze_keymap_bindings(bindings: &[KeymapBinding]) -> Vec<KeymapIssue> {
+	let contexts: Vec<ContextInfo> = bindings
+		.iter()
+		.map(|b| ContextInfo::parse(b.context.as_deref()))
+		.collect();
+	let keys: Vec<String> = bindings.iter().map(|b| normalize_key(&b.key)).collect();
+
+	let mut issues = Vec::new();
+	for (later_idx, later) in bindings.iter().enumerate() {
+		for (earlier_idx, earlier) in bindings.iter().enumerate().take(later_idx) {
+			let (earlier_ctx, later_ctx) = (&contexts[earlier_idx], &contexts[later_idx]);
+			let (earlier_key, later_key) = (&keys[earlier_idx], &keys[later_idx]);
+
+			if earlier_key == later_key {
+				if earlier_ctx == later_ctx {
+					issues.push(KeymapIssue {
+						kind: KeymapIssueKind::Duplicate,
+						binding_idx: later_idx,
+						message: format!(
+							"'{}' in {} already bound to {} at {} (this one wins)",
+							later.key,
+							context_display(later),
+							action_display(&earlier.action),
+							position(earlier)
+						),
+					});
+				} else if let Some(message) = shadow_message(earlier, earlier_ctx, later, later_ctx) {
+					issues.push(KeymapIssue {
+						kind: KeymapIssueKind::Shadow,
+						binding_idx: later_idx,
+						message,
+					});
+				}
+				continue;
+			}
+
+			// -- Prefix of a key sequence (e.g., `ctrl-k` and `ctrl-k ctrl-c`)
+			let (short, long) = if is_key_prefix(earlier_key, later_key) {
+				(earlier, later)
+			} else if is_key_prefix(later_key, earlier_key) {
+				(later, earlier)
+			} else {
+				continue;
+			};
+			if earlier_ctx.overlaps(later_ctx) {
+				issues.push(KeymapIssue {
+					kind: KeymapIssueKind::Prefix,
+					binding_idx: later_idx,
+					message: format!(
+						"'{}' in {} (at {}) is the start of '{}' in {} (at {}), so it waits for the next key",
+						short.key,
+						context_display(short),
+						position(short),
+						long.key,
+						context_display(long),
+						position(long)
+					),
+				});
+			}
+		}
+	}
+
+	issues.sort_by_key(|i| (bindings[i.binding_idx].line, bindings[i.binding_idx].column));
+	issues
+}
+
+/// Build the Markdown cheat sheet, grouped by context (in the keymap order).
+/// Expects the active bindings (see `drop_unbound_bindings`).
+pub fn keymap_cheat_sheet_md(bindings: &[KeymapBinding]) -> String {
+	let mut groups: Vec<(String, Vec<&KeymapBinding>)> = Vec::new();
+	for binding in bindings.iter() {
+		let context = binding.context.clone().unwrap_or_else(|| "Global".to_string());
+		match groups.iter_mut().find(|(c, _)| *c == context) {
+			Some((_, group)) => group.push(binding),
+			None => groups.push((context, vec![binding])),
+		}
+	}
+
+	let mut md = String::from("# Zed Keymap\n");
+	for (context, group) in groups {
+		md.push_str(&format!("\n## {}\n\n", escape_md_cell(&context)));
+		md.push_str("| Key | Action |\n| --- | --- |\n");
+		for binding in group {
+			md.push_str(&format!(
+				"| {} | {} |\n",
+				md_code_span(&binding.key),
+				md_code_span(&action_display(&binding.action))
+			));
+		}
+	}
+
+	md
+}
+
+/// Display of a binding action (e.g., `editor::Format`, `task::Spawn {"task_name":"md-to-html"}`).
+pub fn action_display(action: &Value) -> String {
+	match action {
+		Value::String(name) => name.clone(),
+		Value::Array(items) => match items.as_slice() {
+			[Value::String(name)] => name.clone(),
+			[Value::String(name), args] => format!("{name} {args}"),
+			_ => action.to_string(),
+		},
+		_ => action.to_string(),
+	}
+}
+
+// region:    --- ContextInfo
+
+impl ContextInfo {
+	fn parse(context: Option<&str>) -> Self {
+		let context = context.map(|c| c.split_whitespace().collect::<Vec<_>>().join(" "));
+		let Some(context) = context.filter(|c| !c.is_empty()) else {
+			return Self {
+				names: BTreeSet::new(),
+				predicates: BTreeSet::new(),
+				opaque: None,
+			};
+		};
+
+		if context.contains("||") || context.contains('(') || context.contains('>') {
+			return Self {
+				names: BTreeSet::new(),
+				predicates: BTreeSet::new(),
+				opaque: Some(context),
+			};
+		}
+
+		let mut names = BTreeSet::new();
+		let mut predicates = BTreeSet::new();
+		for term in context.split("&&").map(|t| t.trim()).filter(|t| !t.is_empty()) {
+			if term.chars().all(|c| c.is_alphanumeric() || c == '_') {
+				names.insert(term.to_string());
+			} else {
+				predicates.insert(term.to_string());
+			}
+		}
+
+		Self {
+			names,
+			predicates,
+			opaque: None,
+		}
+	}
+
+	/// The binding contexts can both match at the same time.
+	fn overlaps(&self, other: &ContextInfo) -> bool {
+		if self.opaque.is_some() || other.opaque.is_some() {
+			return self == other;
+		}
+		self.contains(other) || other.contains(self)
+	}
+
+	/// Every focus matching `other` also matches `self` (same or ancestor names, fewer predicates).
+	fn contains(&self, other: &ContextInfo) -> bool {
+		if self.opaque.is_some() || other.opaque.is_some() {
+			return self == other;
+		}
+		let names_ok = self.names.iter().all(|name| {
+			other.names.contains(name)
+				|| other
+					.names
+					.iter()
+					.any(|n| context_ancestors(n).contains(&name.as_str()))
+		});
+		names_ok && self.predicates.is_subset(&other.predicates)
+	}
+
+	/// The depth of the deepest context name (0 for no context).
+	fn depth(&self) -> usize {
+		self.names
+			.iter()
+			.map(|n| context_ancestors(n).len() + 1)
+			.max()
+			.unwrap_or(0)
+	}
+}
+
+// endregion: --- ContextInfo
+
+// region:    --- Support
+
+/// Shadow message when the same key is bound in two overlapping (but different) contexts.
+/// Zed uses the deepest context, then the last binding.
+fn shadow_message(
+	earlier: &KeymapBinding,
+	earlier_ctx: &ContextInfo,
+	later: &KeymapBinding,
+	later_ctx: &ContextInfo,
+) -> Option<String> {
+	if !earlier_ctx.overlaps(later_ctx) {
+		return None;
+	}
+
+	let (winner, winner_ctx, loser, loser_ctx) = if earlier_ctx.depth() > later_ctx.depth() {
+		(earlier, earlier_ctx, later, later_ctx)
+	} else {
+		(later, later_ctx, earlier, earlier_ctx)
+	};
+	// where both match (the narrower context)
+	let both = if loser_ctx.contains(winner_ctx) { winner } else { loser };
+
+	Some(format!(
+		"'{}' {} in {} shadows {} in {} (at {}) when in {}",
+		winner.key,
+		action_display(&winner.action),
+		context_display(winner),
+		action_display(&loser.action),
+		context_display(loser),
+		position(loser),
+		context_display(both)
+	))
+}
+
+fn context_ancestors(name: &str) -> Vec<&'static str> {
+	let mut ancestors = Vec::new();
+	let mut current = name;
+	while let Some((_, parent)) = CONTEXT_PARENTS.iter().find(|(child, _)| *child == current) {
+		ancestors.push(*parent);
+		current = parent;
+	}
+	ancestors
+}
+
+fn position(binding: &KeymapBinding) -> String {
+	format!("{}:{}", binding.line, binding.column)
+}
+
+fn context_display(binding: &KeymapBinding) -> String {
+	match binding.context.as_deref() {
+		Some(context) => format!("'{context}'"),
+		None => "<no context>".to_string(),
+	}
+}
+
+/// Lowercase, with the modifiers of each keystroke sorted (e.g., `shift-cmd-P` => `cmd-shift-p`).
+pub fn normalize_key(key: &str) -> String {
+	key.split_whitespace()
+		.map(|stroke| {
+			let stroke = stroke.to_lowercase();
+			// the `-` key itself (e.g., `cmd--`)
+			let (modifiers, key) = match stroke.strip_suffix("--") {
+				Some(modifiers) => (modifiers.to_string(), "-".to_string()),
+				None => match stroke.rsplit_once('-') {
+					Some((modifiers, key)) => (modifiers.to_string(), key.to_string()),
+					None => (String::new(), stroke.clone()),
+				},
+			};
+			let mut modifiers: Vec<&str> = modifiers.split('-').filter(|m| !m.is_empty()).collect();
+			modifiers.sort_unstable();
+			modifiers.push(&key);
+			modifiers.join("-")
+		})
+		.collect::<Vec<_>>()
+		.join(" ")
+}
+
+/// `short` is the start of the key sequence `long` (whole keystrokes).
+fn is_key_prefix(short: &str, long: &str) -> bool {
+	long.strip_prefix(short).is_some_and(|rest| rest.starts_with(' '))
+}
+
+fn escape_md_cell(text: &str) -> String {
+	text.replace('|', "\\|")
+}
+
+/// A table cell code span, with a fence longer than the backtick runs of the text (e.g., ``` `` ctrl-` `` ```).
+fn md_code_span(text: &str) -> String {
+	let max_run = text.split(|c| c != '`').map(str::len).max().unwrap_or(0);
+	let fence = "`".repeat(max_run + 1);
+	let pad = if max_run > 0 { " " } else { "" };
+	format!("{fence}{pad}{}{pad}{fence}", escape_md_cell(text))
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_support_zed_keymap_report_analyze_simple() -> Result<()> {
+		// -- Setup & Fixtures
+		let content = r#"[
+  {
+    "context": "Workspace",
+    "bindings": {
+      "cmd-k": "workspace::ToggleLeftDock",
+      "cmd-shift-p": "command_palette::Toggle",
+      "ctrl-`": "terminal_panel::ToggleFocus"
+    }
+  },
+  {
+    "context": "Editor",
+    "bindings": {
+      "cmd-k cmd-c": "editor::ToggleComments",
+      "shift-cmd-p": "editor::Format",
+      "cmd-d": ["editor::SelectNext", { "replace_newest": false }]
+    }
+  },
+  {
+    "context": "Editor",
+    "bindings": {
+      "cmd-d": "editor::DuplicateLineDown",
+      "cmd-shift-p": null
+    }
+  },
+  {
+    "context": "Terminal",
+    "bindings": { "cmd-d": "pane::SplitRight" }
+  }
+]"#;
+
+		// -- Exec
+		let bindings = drop_unbound_bindings(parse_keymap_bindings(content)?);
+		let issues = analyze_keymap_bindings(&bindings);
+
+		// -- Check
+		let found: Vec<(usize, KeymapIssueKind)> =
+			issues.iter().map(|i| (bindings[i.binding_idx].line, i.kind)).collect();
+		assert_eq!(
+			found,
+			vec![
+				(13, KeymapIssueKind::Prefix),    // cmd-k (Workspace) / cmd-k cmd-c (Editor)
+				(21, KeymapIssueKind::Duplicate), // cmd-d twice in Editor
+			]
+		);
+		// shift-cmd-p (Editor) unbound by the later `"cmd-shift-p": null` (Editor), so no shadow
+		assert!(bindings.iter().all(|b| b.key != "shift-cmd-p"));
+		assert!(bindings.iter().any(|b| b.key == "cmd-shift-p"));
+
+		let md = keymap_cheat_sheet_md(&bindings);
+		assert!(md.contains("## Editor\n"));
+		assert!(md.contains(r#"| `cmd-d` | `editor::SelectNext {"replace_newest":false}` |"#));
+		assert!(!md.contains("null"));
+		assert!(!md.contains("editor::Format"));
+		assert!(md.contains("| `` ctrl-` `` | `terminal_panel::ToggleFocus` |"));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,29 @@
+// region:    --- Modules
+
+mod common;
+mod keymap;
+mod keymap_report;
+mod settings;
+mod settings_effective;
+mod settings_lint;
+mod snippets;
+mod task_env;
+mod tasks;
+mod theme;
+mod theme_family;
+mod vscode;
+
+pub use common::*;
+pub use keymap::*;
+pub use keymap_report::*;
+pub use settings::*;
+pub use settings_effective::*;
+pub use settings_lint::*;
+pub use snippets::*;
+pub use task_env::*;
+pub use tasks::*;
+pub use theme::*;
+pub use theme_family::*;
+pub use vscode::*;
+
+// endregion: --- Modules
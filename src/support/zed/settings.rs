@@ -0,0 +1,72 @@
+use crate::Result;
+use crate::support::jsons;
+use crate::support::zed::config_dir;
+use simple_fs::SPath;
+
+pub const DISABLE_AI_KEY: &str = "disable_ai";
+
+// region:    --- Types
+
+/// The `disable_ai` values of the global and project settings (None when not set).
+#[derive(Debug)]
+pub struct AiStatus {
+	pub global: Option<bool>,
+	pub project: Option<bool>,
+}
+
+impl AiStatus {
+	/// Project overrides global, and Zed default is AI enabled.
+	pub fn effective_disabled(&self) -> bool {
+		self.project.or(self.global).unwrap_or(false)
+	}
+
+	/// Returns where the effective value comes from.
+	pub fn effective_source(&self) -> &'static str {
+		if self.project.is_some() {
+			"project"
+		} else if self.global.is_some() {
+			"global"
+		} else {
+			"Zed default"
+		}
+	}
+}
+
+// endregion: --- Types
+
+/// Returns the path of the Zed user settings file (`~/.config/zed/settings.json`).
+pub fn settings_json_path() -> Result<SPath> {
+	Ok(config_dir()?.join("settings.json"))
+}
+
+/// Returns the path of the project settings file (`<worktree>/.zed/settings.json`).
+pub fn project_settings_json_path(worktree_root: &str) -> SPath {
+	SPath::new(worktree_root).join(".zed/settings.json")
+}
+
+/// Load the `disable_ai` values from the global and (optionally) project settings.
+pub fn load_ai_status(project_settings_path: Option<&SPath>) -> Result<AiStatus> {
+	let global = read_disable_ai(&settings_json_path()?)?;
+	let project = match project_settings_path {
+		Some(path) => read_disable_ai(path)?,
+		None => None,
+	};
+
+	Ok(AiStatus { global, project })
+}
+
+/// Returns the top level `disable_ai` value of a settings file (None if file or key missing).
+pub fn read_disable_ai(settings_path: &SPath) -> Result<Option<bool>> {
+	if !settings_path.exists() {
+		return Ok(None);
+	}
+	let value = jsons::load_jsons_to_serde_value(settings_path)?;
+	let disable_ai = value.as_ref().and_then(|v| v.get(DISABLE_AI_KEY)).and_then(|v| v.as_bool());
+
+	Ok(disable_ai)
+}
+
+/// Returns the display text for a `disable_ai` value.
+pub fn ai_state_label(disabled: bool) -> &'static str {
+	if disabled { "AI disabled" } else { "AI enabled" }
+}
@@ -0,0 +1,247 @@
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+const LANGUAGES_KEY: &str = "languages";
+
+// region:    --- Types
+
+/// The merged settings, with the source (e.g., "global", "project") of each leaf value.
+/// Leaf values are the non-object values (arrays are replaced as a whole, like Zed does).
+#[derive(Debug, Default)]
+pub struct EffectiveSettings {
+	pub value: Value,
+	/// leaf path (e.g., `["languages", "Rust", "tab_size"]`) -> source
+	sources: HashMap<Vec<String>, String>,
+}
+
+/// One leaf value of the effective settings.
+#[derive(Debug)]
+pub struct EffectiveEntry<'a> {
+	/// The key names (which can contain dots, e.g., `file_types` globs)
+	pub path: Vec<String>,
+	pub value: &'a Value,
+	pub source: &'a str,
+}
+
+// endregion: --- Types
+
+impl EffectiveSettings {
+	/// Deep merge a settings layer (later layers override, objects are merged key by key).
+	pub fn merge_layer(&mut self, layer: &Value, source: &str) {
+		if !self.value.is_object() {
+			self.value = Value::Object(Map::new());
+		}
+		merge_value(&mut self.value, layer, &[], source, &mut self.sources);
+	}
+
+	/// Apply the `languages.<language>` overrides on top of the root settings (like Zed for a buffer of that language).
+	pub fn apply_language(&mut self, language: &str) {
+		let Some(overrides) = self.value.get(LANGUAGES_KEY).and_then(|l| l.get(language)).cloned() else {
+			return;
+		};
+
+		let prefix = vec![LANGUAGES_KEY.to_string(), language.to_string()];
+		let prefix_display = display_path(&prefix);
+		let mut layer_sources = HashMap::new();
+		for (path, source) in self.sources.iter() {
+			if let Some(rel_path) = path.strip_prefix(prefix.as_slice()) {
+				layer_sources.insert(rel_path.to_vec(), format!("{source} via {prefix_display}"));
+			}
+		}
+
+		// merge leaf by leaf to keep the source of each override
+		let mut leaves = Vec::new();
+		collect_leaves(&overrides, &[], &mut leaves);
+		for (rel_path, value) in leaves {
+			let source = layer_sources
+				.get(&rel_path)
+				.cloned()
+				.unwrap_or_else(|| prefix_display.clone());
+			set_path(&mut self.value, &rel_path, value.clone());
+			self.sources.retain(|p, _| !p.starts_with(&rel_path));
+			self.sources.insert(rel_path, source);
+		}
+	}
+
+	/// The leaf values (in the key order), optionally only the ones at or below a path
+	/// (dotted, with the keys containing dots quoted, as in `EffectiveEntry::display_path`).
+	pub fn entries(&self, path_filter: Option<&str>) -> Vec<EffectiveEntry<'_>> {
+		let mut leaves = Vec::new();
+		collect_leaves(&self.value, &[], &mut leaves);
+
+		leaves
+			.into_iter()
+			.filter(|(path, _)| {
+				path_filter.is_none_or(|filter| {
+					let path = display_path(path);
+					path == filter || path.strip_prefix(filter).is_some_and(|rest| rest.starts_with('.'))
+				})
+			})
+			.map(|(path, value)| {
+				let source = self.sources.get(&path).map(|s| s.as_str()).unwrap_or("unknown");
+				EffectiveEntry { path, value, source }
+			})
+			.collect()
+	}
+}
+
+impl EffectiveEntry<'_> {
+	/// The dotted path, with the keys containing dots quoted (e.g., `file_types.Markdown."*.mdx"`).
+	pub fn display_path(&self) -> String {
+		display_path(&self.path)
+	}
+}
+
+// region:    --- Support
+
+fn merge_value(
+	target: &mut Value,
+	layer: &Value,
+	path: &[String],
+	source: &str,
+	sources: &mut HashMap<Vec<String>, String>,
+) {
+	match (target, layer) {
+		(Value::Object(target_obj), Value::Object(layer_obj)) => {
+			for (key, layer_value) in layer_obj.iter() {
+				let child_path = join_path(path, key);
+				match target_obj.get_mut(key) {
+					Some(target_value) if target_value.is_object() && layer_value.is_object() => {
+						merge_value(target_value, layer_value, &child_path, source, sources);
+					}
+					_ => {
+						sources.retain(|p, _| !p.starts_with(&child_path));
+						let mut leaves = Vec::new();
+						collect_leaves(layer_value, &child_path, &mut leaves);
+						for (leaf_path, _) in leaves {
+							sources.insert(leaf_path, source.to_string());
+						}
+						target_obj.insert(key.clone(), layer_value.clone());
+					}
+				}
+			}
+		}
+		(target, layer) => {
+			*target = layer.clone();
+			sources.insert(path.to_vec(), source.to_string());
+		}
+	}
+}
+
+/// The non-object values (and empty objects) with their key paths.
+fn collect_leaves<'a>(value: &'a Value, path: &[String], leaves: &mut Vec<(Vec<String>, &'a Value)>) {
+	match value {
+		Value::Object(obj) if !obj.is_empty() => {
+			for (key, child) in obj.iter() {
+				collect_leaves(child, &join_path(path, key), leaves);
+			}
+		}
+		_ => leaves.push((path.to_vec(), value)),
+	}
+}
+
+/// Set the value at a key path, creating (or replacing with) objects on the way.
+fn set_path(root: &mut Value, path: &[String], value: Value) {
+	let Some((last, parents)) = path.split_last() else {
+		return;
+	};
+	let mut current = root;
+	for part in parents {
+		if !current.is_object() {
+			*current = Value::Object(Map::new());
+		}
+		let Value::Object(obj) = current else { return };
+		current = obj.entry(part.clone()).or_insert_with(|| Value::Object(Map::new()));
+	}
+	if !current.is_object() {
+		*current = Value::Object(Map::new());
+	}
+	if let Value::Object(obj) = current {
+		obj.insert(last.clone(), value);
+	}
+}
+
+fn join_path(path: &[String], key: &str) -> Vec<String> {
+	let mut path = path.to_vec();
+	path.push(key.to_string());
+	path
+}
+
+fn display_path(path: &[String]) -> String {
+	path.iter()
+		.map(|key| {
+			if key.contains('.') {
+				format!("{key:?}")
+			} else {
+				key.clone()
+			}
+		})
+		.collect::<Vec<_>>()
+		.join(".")
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+	use serde_json::json;
+
+	#[test]
+	fn test_support_zed_settings_effective_merge_and_language() -> Result<()> {
+		// -- Setup & Fixtures
+		let global = json!({
+			"tab_size": 4,
+			"format_on_save": "on",
+			"languages": { "Rust": { "tab_size": 2, "hard_tabs": false } }
+		});
+		let project = json!({
+			"format_on_save": "off",
+			"languages": { "Rust": { "hard_tabs": true }, "Plain.Text": { "soft_wrap": "none" } }
+		});
+
+		// -- Exec
+		let mut settings = EffectiveSettings::default();
+		settings.merge_layer(&global, "global");
+		settings.merge_layer(&project, "project");
+		let merged: Vec<(String, String)> = settings
+			.entries(Some("languages"))
+			.into_iter()
+			.map(|e| (e.display_path(), e.source.to_string()))
+			.collect();
+		settings.apply_language("Rust");
+
+		// -- Check
+		assert_eq!(
+			merged,
+			vec![
+				("languages.Rust.tab_size".to_string(), "global".to_string()),
+				("languages.Rust.hard_tabs".to_string(), "project".to_string()),
+				("languages.\"Plain.Text\".soft_wrap".to_string(), "project".to_string()),
+			]
+		);
+		let entries = settings.entries(None);
+		let find = |path: &str| {
+			entries
+				.iter()
+				.find(|e| e.display_path() == path)
+				.map(|e| (e.value.clone(), e.source))
+		};
+		assert_eq!(find("format_on_save"), Some((json!("off"), "project")));
+		assert_eq!(find("tab_size"), Some((json!(2), "global via languages.Rust")));
+		assert_eq!(find("hard_tabs"), Some((json!(true), "project via languages.Rust")));
+		// a language name with a dot is one key (not a nested path)
+		settings.apply_language("Plain.Text");
+		let soft_wrap = settings.entries(Some("soft_wrap"));
+		assert_eq!(soft_wrap.len(), 1);
+		assert_eq!(soft_wrap[0].source, "project via languages.\"Plain.Text\"");
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,284 @@
+use crate::Result;
+use crate::support::{jsons, text};
+use jsonc_parser::ast::{self, ObjectProp};
+use lazy_regex::regex;
+use std::borrow::Cow;
+use std::collections::{BTreeMap, HashMap};
+
+// region:    --- Types
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum LintKind {
+	/// Same key twice in the same object (JSON silently keeps only one)
+	DuplicateKey,
+	/// Same key name at several paths (ambiguous for the text-mode edits)
+	AmbiguousKey,
+	/// A profile value that the text-mode profile editor cannot update
+	NotUpdatable,
+}
+
+impl LintKind {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			LintKind::DuplicateKey => "duplicate-key",
+			LintKind::AmbiguousKey => "ambiguous-key",
+			LintKind::NotUpdatable => "not-updatable",
+		}
+	}
+}
+
+#[derive(Debug)]
+pub struct LintIssue {
+	/// 1-based
+	pub line: usize,
+	/// 1-based (in chars)
+	pub column: usize,
+	pub kind: LintKind,
+	pub message: String,
+}
+
+// endregion: --- Types
+
+/// Lint a Zed settings.json (JSONC) content.
+/// `profile_paths` are the settings paths updated by the profiles (see `toggle-profile`).
+/// Issues are sorted by position.
+pub fn lint_settings(content: &str, profile_paths: &[Vec<String>]) -> Result<Vec<LintIssue>> {
+	let Some(root) = jsons::parse_jsonc_to_ast(content)? else {
+		return Ok(Vec::new());
+	};
+
+	let mut issues = Vec::new();
+
+	// -- Walk the tree (duplicate keys, and the paths of each key name)
+	let mut occurrences: BTreeMap<String, Vec<(String, usize)>> = BTreeMap::new();
+	walk_value(content, &root, "", &mut occurrences, &mut issues);
+
+	// -- Key names at several paths (the items of the same array count as one path)
+	for (name, occs) in occurrences.iter() {
+		let mut paths: Vec<&(String, usize)> = Vec::new();
+		for occ in occs.iter() {
+			if !paths.iter().any(|p| without_indexes(&p.0) == without_indexes(&occ.0)) {
+				paths.push(occ);
+			}
+		}
+		if paths.len() < 2 {
+			continue;
+		}
+		let locations: Vec<String> = paths
+			.iter()
+			.map(|(path, start)| {
+				let (line, column) = text::line_col(content, *start);
+				format!("{path} ({line}:{column})")
+			})
+			.collect();
+		issues.push(issue_at(
+			content,
+			paths[0].1,
+			LintKind::AmbiguousKey,
+			format!(
+				"Key '{name}' appears at {} paths: {}",
+				paths.len(),
+				locations.join(", ")
+			),
+		));
+	}
+
+	// -- Profile values
+	for path in profile_paths {
+		if let Some(issue) = check_profile_path(content, &root, path)? {
+			issues.push(issue);
+		}
+	}
+
+	issues.sort_by_key(|i| (i.line, i.column));
+
+	Ok(issues)
+}
+
+// region:    --- Support
+
+fn walk_value(
+	content: &str,
+	value: &ast::Value,
+	path: &str,
+	occurrences: &mut BTreeMap<String, Vec<(String, usize)>>,
+	issues: &mut Vec<LintIssue>,
+) {
+	match value {
+		ast::Value::Object(obj) => {
+			let mut seen: HashMap<&str, usize> = HashMap::new();
+			for prop in obj.properties.iter() {
+				let name = prop.name.as_str();
+				let prop_path = if path.is_empty() {
+					name.to_string()
+				} else {
+					format!("{path}.{name}")
+				};
+
+				if let Some(first_start) = seen.get(name) {
+					let (first_line, first_column) = text::line_col(content, *first_start);
+					issues.push(issue_at(
+						content,
+						prop.range.start,
+						LintKind::DuplicateKey,
+						format!(
+							"Duplicate key '{prop_path}' (first at {first_line}:{first_column}, only the last one is used)"
+						),
+					));
+				} else {
+					seen.insert(name, prop.range.start);
+				}
+
+				occurrences
+					.entry(name.to_string())
+					.or_default()
+					.push((prop_path.clone(), prop.range.start));
+
+				walk_value(content, &prop.value, &prop_path, occurrences, issues);
+			}
+		}
+		ast::Value::Array(arr) => {
+			for (idx, item) in arr.elements.iter().enumerate() {
+				walk_value(content, item, &format!("{path}[{idx}]"), occurrences, issues);
+			}
+		}
+		_ => (),
+	}
+}
+
+/// The path with the array indexes removed (e.g., `x[1].name` -> `x[].name`).
+fn without_indexes(path: &str) -> Cow<'_, str> {
+	regex!(r"\[\d+\]").replace_all(path, "[]")
+}
+
+/// Check that the text-mode profile editor (`jsons::update_json_value_text_mode`) updates this path.
+fn check_profile_path(content: &str, root: &ast::Value, path: &[String]) -> Result<Option<LintIssue>> {
+	let path_str = path.join(".");
+	let Some(key) = path.last() else {
+		return Ok(None);
+	};
+
+	let Some(prop) = find_prop(root, path) else {
+		return Ok(Some(issue_at(
+			content,
+			0,
+			LintKind::NotUpdatable,
+			format!("Profile key '{path_str}' not found (the profile editor does not add keys)"),
+		)));
+	};
+
+	let matches = jsons::text_mode_value_matches(content, key)?;
+	let is_scalar = matches!(
+		prop.value,
+		ast::Value::StringLit(_) | ast::Value::NumberLit(_) | ast::Value::BooleanLit(_)
+	);
+
+	let message = if !is_scalar {
+		Some(format!(
+			"Profile key '{path_str}' value is not a string, number, or boolean"
+		))
+	} else if matches.len() > 1 {
+		Some(format!(
+			"Profile key '{path_str}' is ambiguous for the profile editor ('{key}' value found {} times)",
+			matches.len()
+		))
+	} else if matches.first().map(|m| m.start) != Some(prop.range.start) {
+		match matches.first() {
+			Some(m) => {
+				let (line, column) = text::line_col(content, m.start);
+				Some(format!(
+					"Profile key '{path_str}' would not be updated (the profile editor matches '{key}' at {line}:{column})"
+				))
+			}
+			None => Some(format!(
+				"Profile key '{path_str}' value format is not supported by the profile editor"
+			)),
+		}
+	} else {
+		None
+	};
+
+	Ok(message.map(|message| issue_at(content, prop.range.start, LintKind::NotUpdatable, message)))
+}
+
+/// Find the property at the path (the last one when duplicated, like JSON).
+fn find_prop<'a>(root: &'a ast::Value<'a>, path: &[String]) -> Option<&'a ObjectProp<'a>> {
+	let mut value = root;
+	let mut found = None;
+	for name in path {
+		let ast::Value::Object(obj) = value else {
+			return None;
+		};
+		let prop = obj.properties.iter().rev().find(|p| p.name.as_str() == name)?;
+		value = &prop.value;
+		found = Some(prop);
+	}
+	found
+}
+
+fn issue_at(content: &str, offset: usize, kind: LintKind, message: String) -> LintIssue {
+	let (line, column) = text::line_col(content, offset);
+	LintIssue {
+		line,
+		column,
+		kind,
+		message,
+	}
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_support_zed_settings_lint_simple() -> Result<()> {
+		// -- Setup & Fixtures
+		let content = r#"{
+  // font
+  "ui_font_size": 16,
+  "buffer_font_size": 15,
+  "tab_size": 4,
+  "theme": "One Dark",
+  "theme": "Ayu Dark",
+  "languages": {
+    "Rust": { "tab_size": 2 }
+  },
+  "lsp_servers": [
+    { "name": "rust-analyzer" },
+    { "name": "taplo" }
+  ]
+}"#;
+		let profile_paths = vec![
+			vec!["ui_font_size".to_string()],
+			vec!["tab_size".to_string()],
+			vec!["languages".to_string()],
+			vec!["vim_mode".to_string()],
+		];
+
+		// -- Exec
+		let issues = lint_settings(content, &profile_paths)?;
+
+		// -- Check
+		let found: Vec<(usize, usize, LintKind)> = issues.iter().map(|i| (i.line, i.column, i.kind)).collect();
+		assert_eq!(
+			found,
+			vec![
+				(1, 1, LintKind::NotUpdatable), // vim_mode not found
+				(5, 3, LintKind::AmbiguousKey), // tab_size at 2 paths
+				(5, 3, LintKind::NotUpdatable), // tab_size ambiguous for the text-mode edit
+				(7, 3, LintKind::DuplicateKey), // theme
+				(8, 3, LintKind::NotUpdatable), // languages is an object
+			]
+		);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,457 @@
+use crate::Result;
+use crate::support::jsons;
+use crate::support::zed::{config_dir, vscode_language_name};
+use jsonc_parser::cst::{CstInputValue, CstObject};
+use lazy_regex::regex;
+use serde_json::Value;
+use simple_fs::SPath;
+
+/// The snippet file language for the snippets of all languages (snippets/snippets.json).
+const GLOBAL_SNIPPETS_LANG: &str = "snippets";
+
+// region:    --- Types
+
+#[derive(Debug)]
+pub struct SnippetInfo {
+	pub name: String,
+	pub prefixes: Vec<String>,
+	pub description: Option<String>,
+}
+
+/// A VS Code snippet converted for a Zed snippet file.
+#[derive(Debug)]
+pub struct ImportedSnippet {
+	/// Zed snippet file language (e.g., `rust` for snippets/rust.json, `snippets` for all languages)
+	pub lang: String,
+	pub name: String,
+	pub prefixes: Vec<String>,
+	pub body: Vec<String>,
+	pub description: Option<String>,
+	/// The VS Code variables converted to placeholders (Zed does not resolve them)
+	pub variables: Vec<String>,
+}
+
+#[derive(Debug, Default)]
+pub struct VscodeSnippetsImport {
+	pub snippets: Vec<ImportedSnippet>,
+	/// (snippet name, reason)
+	pub skipped: Vec<(String, String)>,
+}
+
+// endregion: --- Types
+
+/// Returns the Zed user snippets directory (`~/.config/zed/snippets`).
+pub fn snippets_dir() -> Result<SPath> {
+	Ok(config_dir()?.join("snippets"))
+}
+
+/// Returns the snippet file for a language (e.g., `~/.config/zed/snippets/rust.json`).
+pub fn snippets_file_path(lang: &str) -> Result<SPath> {
+	Ok(snippets_dir()?.join(format!("{lang}.json")))
+}
+
+/// The closing comment markers ending a `TODO: ...` placeholder text.
+const COMMENT_END_MARKERS: &[&str] = &["*/", "-->", "#}", "%}", "*)"];
+
+/// Convert a text into a Zed snippet body (one string per line).
+/// - `\` and `$` are escaped (so they are taken literally).
+/// - `TODO` words become tabstops (`TODO` => `${1:TODO}`, `TODO: some text` => `${1:some text}`).
+///   The text stops at a closing comment marker (e.g., `*/`, `-->`), which is kept after the tabstop.
+pub fn snippet_body_from_text(text: &str) -> Vec<String> {
+	let re = regex!(r"\bTODO\b(?::([^\n]*))?");
+
+	let mut tabstop_idx = 0;
+	let mut body = Vec::new();
+
+	for line in text.lines() {
+		let mut new_line = String::new();
+		let mut last_end = 0;
+
+		for caps in re.captures_iter(line) {
+			let Some(whole) = caps.get(0) else { continue };
+			new_line.push_str(&escape_snippet_text(&line[last_end..whole.start()]));
+
+			tabstop_idx += 1;
+			// Note: Without text, the spaces after `TODO:` are kept (e.g., before a `-->`)
+			let (placeholder, end) = match caps.get(1) {
+				Some(text) => {
+					let comment_end = COMMENT_END_MARKERS
+						.iter()
+						.filter_map(|marker| text.as_str().find(marker))
+						.min()
+						.unwrap_or(text.len());
+					let placeholder = text.as_str()[..comment_end].trim();
+					if placeholder.is_empty() {
+						(placeholder, text.start())
+					} else {
+						let lead = text.as_str().len() - text.as_str().trim_start().len();
+						(placeholder, text.start() + lead + placeholder.len())
+					}
+				}
+				None => ("", whole.end()),
+			};
+			let placeholder = if placeholder.is_empty() { "TODO" } else { placeholder };
+			let placeholder = escape_snippet_text(placeholder).replace('}', "\\}");
+			new_line.push_str(&format!("${{{tabstop_idx}:{placeholder}}}"));
+
+			last_end = end;
+		}
+		new_line.push_str(&escape_snippet_text(&line[last_end..]));
+
+		body.push(new_line);
+	}
+
+	body
+}
+
+/// List the snippets of a snippet file content.
+pub fn list_snippets(content: &str) -> Result<Vec<SnippetInfo>> {
+	let root = jsons::parse_jsonc_to_cst(content)?;
+	let Some(root_obj) = root.object_value() else {
+		return Ok(Vec::new());
+	};
+
+	let mut snippets = Vec::new();
+	for prop in root_obj.properties() {
+		let Some(name) = prop.name().and_then(|n| n.decoded_value().ok()) else {
+			continue;
+		};
+		let Some(obj) = prop.object_value() else {
+			continue;
+		};
+		snippets.push(SnippetInfo {
+			name,
+			prefixes: snippet_prefixes(&obj),
+			description: jsons::cst_object_str_prop(&obj, "description"),
+		});
+	}
+
+	Ok(snippets)
+}
+
+/// Add a snippet to a snippet file content (comments are preserved).
+/// Fails if the name or one of the prefixes is already used in this file.
+pub fn add_snippet(
+	content: &str,
+	name: &str,
+	prefixes: &[String],
+	body: &[String],
+	description: Option<&str>,
+) -> Result<String> {
+	// -- Check duplicates
+	for snippet in list_snippets(content)? {
+		if snippet.name == name {
+			return Err(format!("Snippet '{name}' already exists").into());
+		}
+		if let Some(prefix) = prefixes.iter().find(|p| snippet.prefixes.contains(p)) {
+			return Err(format!("Snippet prefix '{prefix}' already used by snippet '{}'", snippet.name).into());
+		}
+	}
+
+	// -- Add snippet
+	let root = jsons::parse_jsonc_to_cst(content)?;
+	let root_obj = root
+		.object_value_or_create()
+		.ok_or("Zed snippets file root must be an object")?;
+
+	let prefix = match prefixes {
+		[prefix] => CstInputValue::from(prefix.as_str()),
+		_ => CstInputValue::Array(prefixes.iter().map(|p| CstInputValue::from(p.as_str())).collect()),
+	};
+	let body = body.iter().map(|l| CstInputValue::from(l.as_str())).collect();
+	let mut props = vec![
+		("prefix".to_string(), prefix),
+		("body".to_string(), CstInputValue::Array(body)),
+	];
+	if let Some(description) = description {
+		props.push(("description".to_string(), description.into()));
+	}
+	root_obj.append(name, CstInputValue::Object(props));
+
+	Ok(root.to_string())
+}
+
+/// Convert the snippets of a VS Code snippet file (`.code-snippets`, or `snippets/<lang>.json`).
+/// - `scope` (e.g., `"javascript,typescript"`) gives the Zed snippet files, else `default_lang`,
+///   else the global `snippets` file (all languages).
+/// - The VS Code variables (e.g., `$TM_FILENAME`, `${CURRENT_YEAR}`) become placeholders (default or variable name).
+pub fn import_vscode_snippets(vscode: &Value, default_lang: Option<&str>) -> VscodeSnippetsImport {
+	let mut import = VscodeSnippetsImport::default();
+	let Some(snippets) = vscode.as_object() else {
+		return import;
+	};
+
+	for (name, snippet) in snippets.iter() {
+		let prefixes: Vec<String> = match snippet.get("prefix") {
+			Some(Value::String(prefix)) => vec![prefix.clone()],
+			Some(Value::Array(items)) => items.iter().filter_map(|v| v.as_str().map(String::from)).collect(),
+			_ => Vec::new(),
+		};
+		if prefixes.is_empty() {
+			import.skipped.push((name.clone(), "no prefix".to_string()));
+			continue;
+		}
+
+		let lines: Vec<&str> = match snippet.get("body") {
+			Some(Value::String(body)) => body.split('\n').collect(),
+			Some(Value::Array(items)) => items.iter().filter_map(|v| v.as_str()).collect(),
+			_ => Vec::new(),
+		};
+		if lines.is_empty() {
+			import.skipped.push((name.clone(), "no body".to_string()));
+			continue;
+		}
+		let (body, variables) = convert_vscode_variables(&lines);
+
+		let description = snippet.get("description").and_then(|v| v.as_str()).map(String::from);
+
+		let langs: Vec<String> = match snippet.get("scope").and_then(|v| v.as_str()) {
+			Some(scope) => scope
+				.split(',')
+				.map(|id| id.trim())
+				.filter(|id| !id.is_empty())
+				.map(zed_snippet_lang)
+				.collect(),
+			None => vec![
+				default_lang
+					.map(zed_snippet_lang)
+					.unwrap_or_else(|| GLOBAL_SNIPPETS_LANG.to_string()),
+			],
+		};
+		let mut seen_langs = Vec::new();
+		for lang in langs {
+			if seen_langs.contains(&lang) {
+				continue;
+			}
+			seen_langs.push(lang.clone());
+			import.snippets.push(ImportedSnippet {
+				lang,
+				name: name.clone(),
+				prefixes: prefixes.clone(),
+				body: body.clone(),
+				description: description.clone(),
+				variables: variables.clone(),
+			});
+		}
+	}
+
+	import
+}
+
+// region:    --- Support
+
+/// The Zed snippet file language of a VS Code language id (e.g., `typescriptreact` => `tsx`).
+fn zed_snippet_lang(id: &str) -> String {
+	vscode_language_name(id).unwrap_or(id).to_lowercase()
+}
+
+/// Replace the VS Code variables by placeholders, numbered after the existing tabstops
+/// (the same variable gets the same tabstop). Returns the new body and the converted variables.
+fn convert_vscode_variables(lines: &[&str]) -> (Vec<String>, Vec<String>) {
+	let max_tabstop = lines
+		.iter()
+		.flat_map(|line| regex!(r"\$\{?(\d+)").captures_iter(line))
+		.filter_map(|caps| caps.get(1)?.as_str().parse::<u32>().ok())
+		.max()
+		.unwrap_or(0);
+
+	let mut variables: Vec<String> = Vec::new();
+	let mut body = Vec::new();
+
+	for line in lines {
+		let chars: Vec<char> = line.chars().collect();
+		let mut new_line = String::new();
+		let mut idx = 0;
+		while idx < chars.len() {
+			let c = chars[idx];
+			// escaped char
+			if c == '\\' && idx + 1 < chars.len() {
+				new_line.push(c);
+				new_line.push(chars[idx + 1]);
+				idx += 2;
+				continue;
+			}
+			let braced = c == '$' && chars.get(idx + 1) == Some(&'{');
+			let name_start = if braced { idx + 2 } else { idx + 1 };
+			if c != '$'
+				|| !chars
+					.get(name_start)
+					.is_some_and(|c| c.is_ascii_alphabetic() || *c == '_')
+			{
+				new_line.push(c);
+				idx += 1;
+				continue;
+			}
+
+			// -- Variable name
+			let mut end = name_start;
+			while chars.get(end).is_some_and(|c| c.is_ascii_alphanumeric() || *c == '_') {
+				end += 1;
+			}
+			let name: String = chars[name_start..end].iter().collect();
+
+			// -- Default (`${NAME:default}`) or transform (`${NAME/regex/format/}`)
+			let mut default = None;
+			let mut transform = false;
+			if braced {
+				let close = matching_brace(&chars, end);
+				match chars.get(end) {
+					Some(':') => default = Some(chars[end + 1..close].iter().collect::<String>()),
+					Some('/') => transform = true,
+					_ => (),
+				}
+				end = (close + 1).min(chars.len());
+			}
+
+			let label = if transform {
+				format!("{name} (transform dropped)")
+			} else {
+				name.clone()
+			};
+			if !variables.contains(&label) {
+				variables.push(label.clone());
+			}
+			let tabstop = max_tabstop as usize + 1 + variables.iter().position(|v| v == &label).unwrap_or(0);
+			let placeholder = default.unwrap_or(name);
+			new_line.push_str(&format!("${{{tabstop}:{placeholder}}}"));
+
+			idx = end;
+		}
+		body.push(new_line);
+	}
+
+	(body, variables)
+}
+
+/// The index of the `}` closing the `${` before `start` (nested `${...}` skipped), or the end.
+fn matching_brace(chars: &[char], start: usize) -> usize {
+	let mut depth = 0;
+	let mut idx = start;
+	while idx < chars.len() {
+		match chars[idx] {
+			'\\' => idx += 1,
+			'{' => depth += 1,
+			'}' if depth == 0 => return idx,
+			'}' => depth -= 1,
+			_ => (),
+		}
+		idx += 1;
+	}
+	chars.len()
+}
+
+/// Returns the snippet prefixes (`prefix` can be a string or an array of strings).
+fn snippet_prefixes(obj: &CstObject) -> Vec<String> {
+	let Some(value) = obj
+		.get("prefix")
+		.and_then(|p| p.value())
+		.and_then(|v| v.to_serde_value())
+	else {
+		return Vec::new();
+	};
+	match value {
+		serde_json::Value::String(s) => vec![s],
+		serde_json::Value::Array(items) => items.into_iter().filter_map(|v| v.as_str().map(String::from)).collect(),
+		_ => Vec::new(),
+	}
+}
+
+fn escape_snippet_text(text: &str) -> String {
+	text.replace('\\', "\\\\").replace('$', "\\$")
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_support_zed_snippets_body_from_text_todo_and_escape() -> Result<()> {
+		// -- Setup & Fixtures
+		let text = "let price = \"$10\";\nfn TODO() {\n\t// TODO: do something\n}\n\
+			let TODOS = MYTODO + TODO_LIST;\n/* TODO: fix me */ call();\n<!-- TODO: -->";
+
+		// -- Exec
+		let body = snippet_body_from_text(text);
+
+		// -- Check
+		assert_eq!(body[0], r#"let price = "\$10";"#);
+		assert_eq!(body[1], "fn ${1:TODO}() {");
+		assert_eq!(body[2], "\t// ${2:do something}");
+		assert_eq!(body[3], "}");
+		assert_eq!(body[4], "let TODOS = MYTODO + TODO_LIST;");
+		assert_eq!(body[5], "/* ${3:fix me} */ call();");
+		assert_eq!(body[6], "<!-- ${4:TODO} -->");
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_support_zed_snippets_add_reject_dup_prefix() -> Result<()> {
+		// -- Setup & Fixtures
+		let content = r#"{
+  // my snippets
+  "Log": { "prefix": ["log", "lg"], "body": ["println!(\"$1\");"] }
+}"#;
+
+		// -- Exec
+		let res_dup = add_snippet(content, "Log 2", &["lg".to_string()], &["x".to_string()], None);
+		let new_content = add_snippet(
+			content,
+			"Dbg",
+			&["dbg".to_string()],
+			&["dbg!($1);".to_string()],
+			Some("debug"),
+		)?;
+
+		// -- Check
+		assert!(res_dup.is_err());
+		assert!(new_content.contains("// my snippets"));
+		let snippets = list_snippets(&new_content)?;
+		assert_eq!(snippets.len(), 2);
+		assert_eq!(snippets[1].prefixes, vec!["dbg".to_string()]);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_support_zed_snippets_import_vscode_scope_and_variables() -> Result<()> {
+		// -- Setup & Fixtures
+		let vscode = serde_json::json!({
+			"Header": {
+				"scope": "javascript,typescriptreact",
+				"prefix": ["hdr", "header"],
+				"body": ["// $TM_FILENAME (c) ${CURRENT_YEAR}", "${1:name} ${TM_FILENAME/(.*)/${1:/upcase}/} $0"],
+				"description": "File header"
+			},
+			"Log": { "prefix": "log", "body": "console.log(${TM_SELECTED_TEXT:value});" },
+			"Broken": { "body": "x" }
+		});
+
+		// -- Exec
+		let import = import_vscode_snippets(&vscode, None);
+
+		// -- Check
+		let langs: Vec<&str> = import.snippets.iter().map(|s| s.lang.as_str()).collect();
+		assert_eq!(langs, vec!["javascript", "tsx", "snippets"]);
+		let header = &import.snippets[0];
+		assert_eq!(header.prefixes, vec!["hdr".to_string(), "header".to_string()]);
+		assert_eq!(header.body[0], "// ${2:TM_FILENAME} (c) ${3:CURRENT_YEAR}");
+		assert_eq!(header.body[1], "${1:name} ${4:TM_FILENAME} $0");
+		assert_eq!(
+			header.variables,
+			vec!["TM_FILENAME", "CURRENT_YEAR", "TM_FILENAME (transform dropped)"]
+		);
+		assert_eq!(import.snippets[2].body, vec!["console.log(${1:value});".to_string()]);
+		assert_eq!(import.skipped, vec![("Broken".to_string(), "no prefix".to_string())]);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,109 @@
+use crate::Result;
+use std::env;
+
+pub const ZED_FILE: &str = "ZED_FILE";
+pub const ZED_DIRNAME: &str = "ZED_DIRNAME";
+pub const ZED_WORKTREE_ROOT: &str = "ZED_WORKTREE_ROOT";
+pub const ZED_SELECTED_TEXT: &str = "ZED_SELECTED_TEXT";
+pub const ZED_ROW: &str = "ZED_ROW";
+pub const ZED_COLUMN: &str = "ZED_COLUMN";
+
+/// The variables Zed exposes to tasks (empty values are treated as not set).
+#[derive(Debug, Default, Clone)]
+pub struct ZedTaskEnv {
+	pub file: Option<String>,
+	pub dirname: Option<String>,
+	pub worktree_root: Option<String>,
+	pub selected_text: Option<String>,
+	/// Raw, parsed by `row()` / `column()` only by the commands using them (so a bad value does not fail the others).
+	row: Option<String>,
+	column: Option<String>,
+}
+
+impl ZedTaskEnv {
+	/// Read the `ZED_*` environment variables.
+	pub fn from_env() -> Self {
+		Self {
+			file: env_var(ZED_FILE),
+			dirname: env_var(ZED_DIRNAME),
+			worktree_root: env_var(ZED_WORKTREE_ROOT),
+			selected_text: env_var(ZED_SELECTED_TEXT),
+			row: env_var(ZED_ROW),
+			column: env_var(ZED_COLUMN),
+		}
+	}
+
+	/// The 1-based cursor row (`ZED_ROW`), or an error if it is set but not a number.
+	pub fn row(&self) -> Result<Option<u32>> {
+		env_var_num(ZED_ROW, self.row.as_deref())
+	}
+
+	/// The 1-based cursor column (`ZED_COLUMN`), or an error if it is set but not a number.
+	pub fn column(&self) -> Result<Option<u32>> {
+		env_var_num(ZED_COLUMN, self.column.as_deref())
+	}
+}
+
+/// Returns the `arg` value, or the Zed variable value, or an error naming both.
+pub fn arg_or_zed_var(arg: Option<&str>, arg_name: &str, zed_val: Option<&str>, var_name: &str) -> Result<String> {
+	arg.or(zed_val).map(|s| s.to_string()).ok_or_else(|| {
+		crate::Error::custom(format!(
+			"Missing '{arg_name}' argument, and the '{var_name}' environment variable is not set (set by Zed tasks)"
+		))
+	})
+}
+
+// region:    --- Support
+
+fn env_var(name: &str) -> Option<String> {
+	env::var(name).ok().filter(|v| !v.is_empty())
+}
+
+/// The number of an env var value (None if not set or empty).
+fn env_var_num(name: &str, val: Option<&str>) -> Result<Option<u32>> {
+	let Some(val) = val.filter(|v| !v.trim().is_empty()) else {
+		return Ok(None);
+	};
+	let num = val
+		.trim()
+		.parse::<u32>()
+		.map_err(|_| format!("Invalid '{name}' environment variable value '{val}' (must be a number)"))?;
+
+	Ok(Some(num))
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_support_zed_task_env_env_var_num() -> Result<()> {
+		// -- Exec
+		let valid = env_var_num(ZED_ROW, Some(" 42 "))?;
+		let empty = env_var_num(ZED_ROW, Some(""))?;
+		let not_set = env_var_num(ZED_ROW, None)?;
+		let invalid = env_var_num(ZED_ROW, Some("4x"));
+		let column = env_var_num(ZED_COLUMN, Some("7"))?;
+		let column_invalid = env_var_num(ZED_COLUMN, Some("-1"));
+
+		// -- Check
+		assert_eq!(valid, Some(42));
+		assert_eq!(empty, None);
+		assert_eq!(not_set, None);
+		let err = invalid.err().ok_or("should fail")?.to_string();
+		assert!(err.contains("ZED_ROW") && err.contains("4x"), "{err}");
+		assert_eq!(column, Some(7));
+		let err = column_invalid.err().ok_or("should fail")?.to_string();
+		assert!(err.contains("ZED_COLUMN") && err.contains("-1"), "{err}");
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,215 @@
+use crate::Result;
+use crate::support::jsons;
+use jsonc_parser::cst::{CstInputValue, CstNode, CstObject};
+use serde_json::{Value, json};
+
+pub const TASK_BIN: &str = "jc-zed-tasks";
+pub const TASK_LABEL_PREFIX: &str = "jc-zed-tasks: ";
+
+// region:    --- Types
+
+/// A Zed task definition (one entry of `tasks.json`) for one of our subcommands.
+#[derive(Debug, Clone, Copy)]
+pub struct ZedTaskDef {
+	/// Label suffix (the full label is `TASK_LABEL_PREFIX` + name)
+	pub name: &'static str,
+	pub args: &'static [&'static str],
+	/// Zed `reveal` ("always" | "no_focus" | "never")
+	pub reveal: &'static str,
+	/// Zed `hide` ("never" | "always" | "on_success")
+	pub hide: &'static str,
+}
+
+impl ZedTaskDef {
+	pub fn label(self) -> String {
+		format!("{TASK_LABEL_PREFIX}{}", self.name)
+	}
+
+	pub fn to_serde_value(self) -> Value {
+		json!({
+			"label": self.label(),
+			"command": TASK_BIN,
+			"args": self.args,
+			"reveal": self.reveal,
+			"hide": self.hide,
+		})
+	}
+
+	/// Note: Built by hand to keep the property order (label first) in tasks.json.
+	fn to_cst_input(self) -> CstInputValue {
+		let args = self.args.iter().map(|a| CstInputValue::from(*a)).collect();
+		CstInputValue::Object(vec![
+			("label".to_string(), self.label().into()),
+			("command".to_string(), TASK_BIN.into()),
+			("args".to_string(), CstInputValue::Array(args)),
+			("reveal".to_string(), self.reveal.into()),
+			("hide".to_string(), self.hide.into()),
+		])
+	}
+}
+
+#[derive(Debug, Default)]
+pub struct TasksMergeReport {
+	pub added: Vec<String>,
+	pub updated: Vec<String>,
+	pub unchanged: Vec<String>,
+	pub removed: Vec<String>,
+}
+
+impl TasksMergeReport {
+	pub fn has_changes(&self) -> bool {
+		!self.added.is_empty() || !self.updated.is_empty() || !self.removed.is_empty()
+	}
+}
+
+// endregion: --- Types
+
+/// The built-in catalog, one task per subcommand.
+pub const TASK_CATALOG: &[ZedTaskDef] = &[
+	ZedTaskDef {
+		name: "md-to-html",
+		args: &["md-to-html", "--file", "$ZED_FILE"],
+		reveal: "never",
+		hide: "on_success",
+	},
+	ZedTaskDef {
+		name: "save-clipboard-image",
+		args: &["save-clipboard-image", "--dir", "$ZED_DIRNAME", "--copy-md-ref"],
+		reveal: "never",
+		hide: "on_success",
+	},
+	ZedTaskDef {
+		name: "zed-toggle-ai",
+		args: &["zed-toggle-ai"],
+		reveal: "never",
+		hide: "on_success",
+	},
+	ZedTaskDef {
+		name: "toggle-profile",
+		args: &["toggle-profile"],
+		reveal: "never",
+		hide: "on_success",
+	},
+	ZedTaskDef {
+		name: "tmux-run-aip",
+		args: &["tmux-run-aip", "--dir", "$ZED_WORKTREE_ROOT"],
+		reveal: "never",
+		hide: "on_success",
+	},
+];
+
+/// Merge the task definitions into the tasks.json content (keyed by label).
+/// - Existing entries with the same label are replaced (only if different).
+/// - User entries and comments are preserved.
+pub fn install_tasks(content: &str, tasks: &[ZedTaskDef]) -> Result<(String, TasksMergeReport)> {
+	let root = jsons::parse_jsonc_to_cst(content)?;
+	let tasks_arr = root
+		.array_value_or_create()
+		.ok_or("Zed tasks.json root must be an array")?;
+
+	let mut report = TasksMergeReport::default();
+
+	for task in tasks {
+		let label = task.label();
+		let existing = find_task_obj(&tasks_arr.elements(), &label);
+
+		match existing {
+			Some(obj) if obj.to_serde_value().as_ref() == Some(&task.to_serde_value()) => {
+				report.unchanged.push(label);
+			}
+			Some(obj) => {
+				obj.replace_with(task.to_cst_input());
+				report.updated.push(label);
+			}
+			None => {
+				tasks_arr.append(task.to_cst_input());
+				report.added.push(label);
+			}
+		}
+	}
+
+	Ok((root.to_string(), report))
+}
+
+/// Remove the task definitions (by label) from the tasks.json content.
+pub fn uninstall_tasks(content: &str, tasks: &[ZedTaskDef]) -> Result<(String, TasksMergeReport)> {
+	let root = jsons::parse_jsonc_to_cst(content)?;
+	let mut report = TasksMergeReport::default();
+
+	let Some(tasks_arr) = root.array_value() else {
+		return Ok((content.to_string(), report));
+	};
+
+	for task in tasks {
+		let label = task.label();
+		if let Some(obj) = find_task_obj(&tasks_arr.elements(), &label) {
+			obj.remove();
+			report.removed.push(label);
+		}
+	}
+
+	Ok((root.to_string(), report))
+}
+
+// region:    --- Support
+
+fn find_task_obj(elements: &[CstNode], label: &str) -> Option<CstObject> {
+	elements
+		.iter()
+		.filter_map(|el| el.as_object())
+		.find(|obj| jsons::cst_object_str_prop(obj, "label").as_deref() == Some(label))
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_support_zed_tasks_install_idempotent_and_keep_user() -> Result<()> {
+		// -- Setup & Fixtures
+		let content = r#"[
+  // my own task
+  {
+    "label": "my task",
+    "command": "echo"
+  }
+]
+"#;
+
+		// -- Exec
+		let (content_1, report_1) = install_tasks(content, TASK_CATALOG)?;
+		let (content_2, report_2) = install_tasks(&content_1, TASK_CATALOG)?;
+
+		// -- Check
+		assert_eq!(report_1.added.len(), TASK_CATALOG.len());
+		assert!(!report_2.has_changes());
+		assert_eq!(content_1, content_2);
+		assert!(content_1.contains("// my own task"));
+		assert!(content_1.contains(r#""label": "my task""#));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_support_zed_tasks_uninstall_simple() -> Result<()> {
+		// -- Setup & Fixtures
+		let (content, _) = install_tasks("[]", TASK_CATALOG)?;
+
+		// -- Exec
+		let (content, report) = uninstall_tasks(&content, TASK_CATALOG)?;
+
+		// -- Check
+		assert_eq!(report.removed.len(), TASK_CATALOG.len());
+		assert!(!content.contains(TASK_LABEL_PREFIX));
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,187 @@
+use crate::Result;
+use serde_json::{Value, json};
+
+pub const THEME_KEY: &str = "theme";
+
+// region:    --- Types
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum ThemeMode {
+	Light,
+	Dark,
+	System,
+}
+
+impl ThemeMode {
+	pub fn as_str(&self) -> &'static str {
+		match self {
+			ThemeMode::Light => "light",
+			ThemeMode::Dark => "dark",
+			ThemeMode::System => "system",
+		}
+	}
+
+	fn from_str(mode: &str) -> Result<Self> {
+		match mode {
+			"light" => Ok(ThemeMode::Light),
+			"dark" => Ok(ThemeMode::Dark),
+			"system" => Ok(ThemeMode::System),
+			_ => Err(format!("Unknown Zed theme mode '{mode}'").into()),
+		}
+	}
+}
+
+/// The Zed `theme` setting, either a plain theme name, or `{mode, light, dark}`.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum ThemeSetting {
+	Plain(String),
+	Modal { mode: ThemeMode, light: String, dark: String },
+}
+
+impl ThemeSetting {
+	pub fn from_value(value: &Value) -> Result<Self> {
+		match value {
+			Value::String(name) => Ok(ThemeSetting::Plain(name.clone())),
+			Value::Object(obj) => {
+				let get_str = |name: &str| -> Result<String> {
+					obj.get(name)
+						.and_then(|v| v.as_str())
+						.map(String::from)
+						.ok_or_else(|| format!("Zed theme setting missing '{name}' string").into())
+				};
+				let mode = match obj.get("mode").and_then(|v| v.as_str()) {
+					Some(mode) => ThemeMode::from_str(mode)?,
+					// Zed default mode
+					None => ThemeMode::System,
+				};
+				Ok(ThemeSetting::Modal {
+					mode,
+					light: get_str("light")?,
+					dark: get_str("dark")?,
+				})
+			}
+			_ => Err(format!("Unsupported Zed theme setting: {value}").into()),
+		}
+	}
+
+	pub fn to_value(&self) -> Value {
+		match self {
+			ThemeSetting::Plain(name) => json!(name),
+			ThemeSetting::Modal { mode, light, dark } => json!({
+				"mode": mode.as_str(),
+				"light": light,
+				"dark": dark,
+			}),
+		}
+	}
+
+	/// The theme currently in use (for `system` mode, the dark one is assumed).
+	pub fn active(&self) -> &str {
+		match self {
+			ThemeSetting::Plain(name) => name,
+			ThemeSetting::Modal { mode, light, dark } => match mode {
+				ThemeMode::Light => light,
+				ThemeMode::Dark | ThemeMode::System => dark,
+			},
+		}
+	}
+
+	/// Set the active theme (the slot of the current mode for the modal form).
+	pub fn with_active(self, name: impl Into<String>) -> Self {
+		let name = name.into();
+		match self {
+			ThemeSetting::Plain(_) => ThemeSetting::Plain(name),
+			ThemeSetting::Modal { mode, light, dark } => match mode {
+				ThemeMode::Light => ThemeSetting::Modal { mode, light: name, dark },
+				ThemeMode::Dark | ThemeMode::System => ThemeSetting::Modal { mode, light, dark: name },
+			},
+		}
+	}
+
+	/// Convert to the modal form with this mode.
+	/// From a plain theme, the name goes to the light or dark slot (guessed from the name),
+	/// and the other slot comes from the defaults (or the same theme).
+	pub fn with_mode(self, mode: ThemeMode, default_light: Option<&str>, default_dark: Option<&str>) -> Self {
+		match self {
+			ThemeSetting::Plain(name) => {
+				let is_light = name.to_lowercase().contains("light");
+				let (light, dark) = if is_light {
+					(name.clone(), default_dark.unwrap_or(&name).to_string())
+				} else {
+					(default_light.unwrap_or(&name).to_string(), name.clone())
+				};
+				ThemeSetting::Modal { mode, light, dark }
+			}
+			ThemeSetting::Modal { light, dark, .. } => ThemeSetting::Modal { mode, light, dark },
+		}
+	}
+
+	/// Convert to the plain form (with the active theme).
+	pub fn into_plain(self) -> Self {
+		ThemeSetting::Plain(self.active().to_string())
+	}
+}
+
+// endregion: --- Types
+
+/// Returns the next (step 1) or previous (step -1) theme of the list.
+/// If the current theme is not in the list, starts from the first (next) or last (prev).
+pub fn cycle_theme<'a>(list: &'a [String], current: &str, step: isize) -> Result<&'a str> {
+	if list.is_empty() {
+		return Err("No themes in the 'zed_theme.list' config".into());
+	}
+	let len = list.len() as isize;
+
+	let next_idx = match list.iter().position(|t| t == current) {
+		Some(idx) => (idx as isize + step).rem_euclid(len),
+		None if step >= 0 => 0,
+		None => len - 1,
+	};
+
+	Ok(&list[next_idx as usize])
+}
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_support_zed_theme_setting_convert_forms() -> Result<()> {
+		// -- Setup & Fixtures
+		let setting = ThemeSetting::from_value(&json!("One Dark"))?;
+
+		// -- Exec
+		let modal = setting.with_mode(ThemeMode::Light, Some("One Light"), None);
+		let modal = modal.with_active("Ayu Light");
+		let plain = modal.clone().into_plain();
+
+		// -- Check
+		assert_eq!(
+			modal.to_value(),
+			json!({"mode": "light", "light": "Ayu Light", "dark": "One Dark"})
+		);
+		assert_eq!(plain, ThemeSetting::Plain("Ayu Light".to_string()));
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_support_zed_theme_cycle_simple() -> Result<()> {
+		// -- Setup & Fixtures
+		let list = vec!["A".to_string(), "B".to_string(), "C".to_string()];
+
+		// -- Exec & Check
+		assert_eq!(cycle_theme(&list, "C", 1)?, "A");
+		assert_eq!(cycle_theme(&list, "A", -1)?, "C");
+		assert_eq!(cycle_theme(&list, "Other", -1)?, "C");
+		assert_eq!(cycle_theme(&list, "Other", 1)?, "A");
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests
@@ -0,0 +1,91 @@
+use crate::Result;
+use crate::support::jsons;
+use crate::support::zed::config_dir;
+use serde::Deserialize;
+use serde_json::{Map, Value};
+use simple_fs::{SPath, list_files};
+
+// region:    --- Types
+
+/// A Zed theme family file (e.g., `~/.config/zed/themes/one.json`).
+#[derive(Debug, Deserialize)]
+pub struct ZedThemeFamily {
+	pub name: String,
+	pub themes: Vec<ZedThemeDef>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ZedThemeDef {
+	pub name: String,
+	pub appearance: Option<String>,
+	#[serde(default)]
+	pub style: Map<String, Value>,
+}
+
+impl ZedThemeDef {
+	/// Returns the style color (e.g., `terminal.ansi.red`) as `#rrggbb` (alpha dropped).
+	pub fn style_color(&self, key: &str) -> Option<String> {
+		let color = self.style.get(key)?.as_str()?;
+		let hex = color.strip_prefix('#')?;
+		if !hex.chars().all(|c| c.is_ascii_hexdigit()) {
+			return None;
+		}
+		match hex.len() {
+			// Shorthand `#rgb` and `#rgba` (e.g., `#f80` => `#ff8800`)
+			3 | 4 => Some(format!(
+				"#{}",
+				hex.chars().take(3).flat_map(|c| [c, c]).collect::<String>()
+			)),
+			6 => Some(format!("#{hex}")),
+			8 => Some(format!("#{}", &hex[..6])),
+			_ => None,
+		}
+	}
+}
+
+// endregion: --- Types
+
+/// Returns the Zed user themes directory (`~/.config/zed/themes`).
+pub fn themes_dir() -> Result<SPath> {
+	Ok(config_dir()?.join("themes"))
+}
+
+pub fn load_theme_family(path: &SPath) -> Result<ZedThemeFamily> {
+	let value = jsons::load_jsons_to_serde_value(path)?.ok_or_else(|| format!("Zed theme file is empty: {path}"))?;
+	let family: ZedThemeFamily = serde_json::from_value(value)
+		.map_err(|err| crate::Error::custom(format!("Invalid Zed theme file {path}. Cause: {err}")))?;
+
+	Ok(family)
+}
+
+/// Find the theme file:
+/// - `from` as a path, or as a file name in the themes dir (with or without `.json`)
+/// - or, when no `from`, the first file of the themes dir having the theme name.
+pub fn find_theme_file(from: Option<&str>, theme_name: &str) -> Result<SPath> {
+	let themes_dir = themes_dir()?;
+
+	if let Some(from) = from {
+		let candidates = [
+			SPath::new(from),
+			themes_dir.join(from),
+			themes_dir.join(format!("{from}.json")),
+		];
+		return candidates
+			.into_iter()
+			.find(|p| p.is_file())
+			.ok_or_else(|| format!("Zed theme file '{from}' not found (also checked in {themes_dir})").into());
+	}
+
+	if !themes_dir.exists() {
+		return Err(format!("Zed themes directory not found at: {themes_dir}").into());
+	}
+	for file in list_files(&themes_dir, Some(&["*.json"]), None)? {
+		if let Ok(family) = load_theme_family(&file)
+			&& family.themes.iter().any(|t| t.name == theme_name)
+		{
+			return Ok(file);
+		}
+	}
+
+	Err(format!("No theme '{theme_name}' found in {themes_dir}").into())
+}
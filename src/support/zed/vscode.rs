@@ -0,0 +1,858 @@
+use crate::support::zed::KeymapActionBinding;
+use serde_json::{Map, Value, json};
+
+// region:    --- Types
+
+/// A VS Code setting mapped to a Zed setting.
+struct SettingMapping {
+	vscode: &'static str,
+	/// Dotted Zed settings path (e.g., `terminal.font_size`)
+	zed: &'static str,
+	/// Can be set in a `languages.<Language>` block (for the VS Code `"[lang]"` blocks)
+	language: bool,
+	/// Convert the VS Code value (with the whole VS Code settings, for related keys), None if not supported.
+	convert: fn(&Value, &Map<String, Value>) -> Option<Value>,
+}
+
+/// A VS Code command mapped to a Zed action.
+struct CommandMapping {
+	vscode: &'static str,
+	zed: &'static str,
+	context: &'static str,
+}
+
+/// A Zed setting from a VS Code setting.
+#[derive(Debug)]
+pub struct VscodeSettingChange {
+	pub vscode_key: String,
+	pub zed_path: Vec<String>,
+	pub value: Value,
+}
+
+/// A VS Code setting or keybinding that was not imported.
+#[derive(Debug)]
+pub struct VscodeUnmapped {
+	pub key: String,
+	pub reason: String,
+}
+
+#[derive(Debug, Default)]
+pub struct VscodeSettingsImport {
+	pub changes: Vec<VscodeSettingChange>,
+	pub unmapped: Vec<VscodeUnmapped>,
+}
+
+#[derive(Debug, Default)]
+pub struct VscodeKeybindingsImport {
+	pub bindings: Vec<KeymapActionBinding>,
+	pub unmapped: Vec<VscodeUnmapped>,
+}
+
+// endregion: --- Types
+
+// region:    --- Mappings
+
+const SETTING_MAPPINGS: &[SettingMapping] = &[
+	SettingMapping {
+		vscode: "editor.fontSize",
+		zed: "buffer_font_size",
+		language: false,
+		convert: number,
+	},
+	SettingMapping {
+		vscode: "editor.fontFamily",
+		zed: "buffer_font_family",
+		language: false,
+		convert: first_font_family,
+	},
+	SettingMapping {
+		vscode: "editor.lineHeight",
+		zed: "buffer_line_height",
+		language: false,
+		convert: line_height,
+	},
+	SettingMapping {
+		vscode: "editor.tabSize",
+		zed: "tab_size",
+		language: true,
+		convert: number,
+	},
+	SettingMapping {
+		vscode: "editor.insertSpaces",
+		zed: "hard_tabs",
+		language: true,
+		convert: inverted_bool,
+	},
+	SettingMapping {
+		vscode: "editor.formatOnSave",
+		zed: "format_on_save",
+		language: true,
+		convert: on_off,
+	},
+	SettingMapping {
+		vscode: "editor.wordWrap",
+		zed: "soft_wrap",
+		language: true,
+		convert: soft_wrap,
+	},
+	SettingMapping {
+		vscode: "editor.wordWrapColumn",
+		zed: "preferred_line_length",
+		language: true,
+		convert: number,
+	},
+	SettingMapping {
+		vscode: "editor.rulers",
+		zed: "wrap_guides",
+		language: true,
+		convert: rulers,
+	},
+	SettingMapping {
+		vscode: "editor.renderWhitespace",
+		zed: "show_whitespaces",
+		language: true,
+		convert: show_whitespaces,
+	},
+	SettingMapping {
+		vscode: "editor.lineNumbers",
+		zed: "relative_line_numbers",
+		language: false,
+		convert: relative_line_numbers,
+	},
+	SettingMapping {
+		vscode: "editor.cursorBlinking",
+		zed: "cursor_blink",
+		language: false,
+		convert: cursor_blink,
+	},
+	SettingMapping {
+		vscode: "editor.cursorStyle",
+		zed: "cursor_shape",
+		language: false,
+		convert: cursor_shape,
+	},
+	SettingMapping {
+		vscode: "editor.minimap.enabled",
+		zed: "minimap.show",
+		language: false,
+		convert: minimap_show,
+	},
+	SettingMapping {
+		vscode: "files.autoSave",
+		zed: "autosave",
+		language: false,
+		convert: autosave,
+	},
+	SettingMapping {
+		vscode: "files.trimTrailingWhitespace",
+		zed: "remove_trailing_whitespace_on_save",
+		language: true,
+		convert: boolean,
+	},
+	SettingMapping {
+		vscode: "files.insertFinalNewline",
+		zed: "ensure_final_newline_on_save",
+		language: true,
+		convert: boolean,
+	},
+	SettingMapping {
+		vscode: "files.exclude",
+		zed: "file_scan_exclusions",
+		language: false,
+		convert: files_exclude,
+	},
+	SettingMapping {
+		vscode: "terminal.integrated.fontSize",
+		zed: "terminal.font_size",
+		language: false,
+		convert: number,
+	},
+	SettingMapping {
+		vscode: "terminal.integrated.fontFamily",
+		zed: "terminal.font_family",
+		language: false,
+		convert: first_font_family,
+	},
+	SettingMapping {
+		vscode: "vim.useSystemClipboard",
+		zed: "vim.use_system_clipboard",
+		language: false,
+		convert: vim_system_clipboard,
+	},
+	SettingMapping {
+		vscode: "vim.smartRelativeLine",
+		zed: "vim.toggle_relative_line_numbers",
+		language: false,
+		convert: boolean,
+	},
+];
+
+/// VS Code settings read by another mapping (not reported as unmapped).
+const RELATED_SETTINGS: &[&str] = &["files.autoSaveDelay"];
+
+/// The Zed default `file_scan_exclusions` (setting the key replaces them, so they are kept in the imported list).
+const ZED_DEFAULT_FILE_SCAN_EXCLUSIONS: &[&str] = &[
+	"**/.git",
+	"**/.svn",
+	"**/.hg",
+	"**/.jj",
+	"**/CVS",
+	"**/.DS_Store",
+	"**/Thumbs.db",
+	"**/.classpath",
+	"**/.settings",
+];
+
+/// The setting prefixes of the VS Code vim extensions (VSCodeVim, VSCode Neovim), which turn on `vim_mode`.
+const VIM_EXTENSION_PREFIXES: &[&str] = &["vim.", "vscode-neovim."];
+
+/// VS Code language ids to Zed language names (for the `"[lang]"` blocks).
+const LANGUAGE_NAMES: &[(&str, &str)] = &[
+	("c", "C"),
+	("cpp", "C++"),
+	("css", "CSS"),
+	("elixir", "Elixir"),
+	("go", "Go"),
+	("html", "HTML"),
+	("java", "Java"),
+	("javascript", "JavaScript"),
+	("javascriptreact", "JavaScript"),
+	("json", "JSON"),
+	("jsonc", "JSONC"),
+	("lua", "Lua"),
+	("markdown", "Markdown"),
+	("python", "Python"),
+	("ruby", "Ruby"),
+	("rust", "Rust"),
+	("shellscript", "Shell Script"),
+	("sql", "SQL"),
+	("toml", "TOML"),
+	("typescript", "TypeScript"),
+	("typescriptreact", "TSX"),
+	("yaml", "YAML"),
+];
+
+const COMMAND_MAPPINGS: &[CommandMapping] = &[
+	// -- Workspace
+	CommandMapping {
+		vscode: "workbench.action.showCommands",
+		zed: "command_palette::Toggle",
+		context: "Workspace",
+	},
+	CommandMapping {
+		vscode: "workbench.action.quickOpen",
+		zed: "file_finder::Toggle",
+		context: "Workspace",
+	},
+	CommandMapping {
+		vscode: "workbench.action.files.save",
+		zed: "workspace::Save",
+		context: "Workspace",
+	},
+	CommandMapping {
+		vscode: "workbench.action.files.saveAll",
+		zed: "workspace::SaveAll",
+		context: "Workspace",
+	},
+	CommandMapping {
+		vscode: "workbench.action.files.newUntitledFile",
+		zed: "workspace::NewFile",
+		context: "Workspace",
+	},
+	CommandMapping {
+		vscode: "workbench.action.toggleSidebarVisibility",
+		zed: "workspace::ToggleLeftDock",
+		context: "Workspace",
+	},
+	CommandMapping {
+		vscode: "workbench.action.togglePanel",
+		zed: "workspace::ToggleBottomDock",
+		context: "Workspace",
+	},
+	CommandMapping {
+		vscode: "workbench.action.terminal.toggleTerminal",
+		zed: "terminal_panel::ToggleFocus",
+		context: "Workspace",
+	},
+	CommandMapping {
+		vscode: "workbench.view.explorer",
+		zed: "project_panel::ToggleFocus",
+		context: "Workspace",
+	},
+	CommandMapping {
+		vscode: "workbench.action.findInFiles",
+		zed: "pane::DeploySearch",
+		context: "Workspace",
+	},
+	CommandMapping {
+		vscode: "workbench.action.showAllSymbols",
+		zed: "project_symbols::Toggle",
+		context: "Workspace",
+	},
+	CommandMapping {
+		vscode: "workbench.action.openSettings",
+		zed: "zed::OpenSettings",
+		context: "Workspace",
+	},
+	// -- Pane
+	CommandMapping {
+		vscode: "workbench.action.closeActiveEditor",
+		zed: "pane::CloseActiveItem",
+		context: "Pane",
+	},
+	CommandMapping {
+		vscode: "workbench.action.nextEditor",
+		zed: "pane::ActivateNextItem",
+		context: "Pane",
+	},
+	CommandMapping {
+		vscode: "workbench.action.previousEditor",
+		zed: "pane::ActivatePreviousItem",
+		context: "Pane",
+	},
+	CommandMapping {
+		vscode: "workbench.action.splitEditor",
+		zed: "pane::SplitRight",
+		context: "Pane",
+	},
+	CommandMapping {
+		vscode: "actions.find",
+		zed: "buffer_search::Deploy",
+		context: "Pane",
+	},
+	CommandMapping {
+		vscode: "editor.action.startFindReplaceAction",
+		zed: "buffer_search::DeployReplace",
+		context: "Pane",
+	},
+	// -- Editor
+	CommandMapping {
+		vscode: "workbench.action.gotoLine",
+		zed: "go_to_line::Toggle",
+		context: "Editor",
+	},
+	CommandMapping {
+		vscode: "workbench.action.gotoSymbol",
+		zed: "outline::Toggle",
+		context: "Editor",
+	},
+	CommandMapping {
+		vscode: "editor.action.formatDocument",
+		zed: "editor::Format",
+		context: "Editor",
+	},
+	CommandMapping {
+		vscode: "editor.action.commentLine",
+		zed: "editor::ToggleComments",
+		context: "Editor",
+	},
+	CommandMapping {
+		vscode: "editor.action.rename",
+		zed: "editor::Rename",
+		context: "Editor",
+	},
+	CommandMapping {
+		vscode: "editor.action.revealDefinition",
+		zed: "editor::GoToDefinition",
+		context: "Editor",
+	},
+	CommandMapping {
+		vscode: "editor.action.goToReferences",
+		zed: "editor::FindAllReferences",
+		context: "Editor",
+	},
+	CommandMapping {
+		vscode: "editor.action.quickFix",
+		zed: "editor::ToggleCodeActions",
+		context: "Editor",
+	},
+	CommandMapping {
+		vscode: "editor.action.showHover",
+		zed: "editor::Hover",
+		context: "Editor",
+	},
+	CommandMapping {
+		vscode: "editor.action.triggerSuggest",
+		zed: "editor::ShowCompletions",
+		context: "Editor",
+	},
+	CommandMapping {
+		vscode: "editor.action.copyLinesDownAction",
+		zed: "editor::DuplicateLineDown",
+		context: "Editor",
+	},
+	CommandMapping {
+		vscode: "editor.action.moveLinesUpAction",
+		zed: "editor::MoveLineUp",
+		context: "Editor",
+	},
+	CommandMapping {
+		vscode: "editor.action.moveLinesDownAction",
+		zed: "editor::MoveLineDown",
+		context: "Editor",
+	},
+	CommandMapping {
+		vscode: "editor.action.deleteLines",
+		zed: "editor::DeleteLine",
+		context: "Editor",
+	},
+	CommandMapping {
+		vscode: "editor.action.addSelectionToNextFindMatch",
+		zed: "editor::SelectNext",
+		context: "Editor",
+	},
+	CommandMapping {
+		vscode: "editor.action.insertCursorAbove",
+		zed: "editor::AddSelectionAbove",
+		context: "Editor",
+	},
+	CommandMapping {
+		vscode: "editor.action.insertCursorBelow",
+		zed: "editor::AddSelectionBelow",
+		context: "Editor",
+	},
+];
+
+// endregion: --- Mappings
+
+/// Map the VS Code settings (the parsed settings.json) to Zed settings.
+/// The `"[lang]"` blocks go to `languages.<Language>` (for the settings Zed supports per language).
+pub fn import_vscode_settings(vscode: &Value) -> VscodeSettingsImport {
+	let mut import = VscodeSettingsImport::default();
+	let Some(settings) = vscode.as_object() else {
+		return import;
+	};
+
+	for (key, value) in settings.iter() {
+		if key.starts_with('[') {
+			import_language_block(key, value, &mut import);
+			continue;
+		}
+		match map_setting(key, value, settings) {
+			Ok(Some((mapping, value))) => import.changes.push(VscodeSettingChange {
+				vscode_key: key.clone(),
+				zed_path: mapping.zed.split('.').map(|s| s.to_string()).collect(),
+				value,
+			}),
+			Ok(None) => (),
+			Err(reason) => import.unmapped.push(VscodeUnmapped {
+				key: key.clone(),
+				reason,
+			}),
+		}
+	}
+
+	// -- Vim extension
+	if let Some(vim_key) = settings
+		.keys()
+		.find(|k| VIM_EXTENSION_PREFIXES.iter().any(|prefix| k.starts_with(prefix)))
+	{
+		import.changes.push(VscodeSettingChange {
+			vscode_key: vim_key.clone(),
+			zed_path: vec!["vim_mode".to_string()],
+			value: Value::Bool(true),
+		});
+	}
+
+	import
+}
+
+/// Translate the VS Code keybindings (the parsed keybindings.json) to Zed bindings.
+/// The Zed context comes from the command mapping (the VS Code `when` clauses are not translated).
+pub fn import_vscode_keybindings(vscode: &Value) -> VscodeKeybindingsImport {
+	let mut import = VscodeKeybindingsImport::default();
+	let Some(items) = vscode.as_array() else {
+		return import;
+	};
+
+	for item in items {
+		let key = item.get("key").and_then(|v| v.as_str()).unwrap_or_default();
+		let command = item.get("command").and_then(|v| v.as_str()).unwrap_or_default();
+		let display = format!("{key} {command}");
+
+		let reason = if command.starts_with('-') {
+			Some("removing a default binding is not supported".to_string())
+		} else if item.get("args").is_some() {
+			Some("command with args".to_string())
+		} else {
+			None
+		};
+		if let Some(reason) = reason {
+			import.unmapped.push(VscodeUnmapped { key: display, reason });
+			continue;
+		}
+
+		let Some(mapping) = COMMAND_MAPPINGS.iter().find(|m| m.vscode == command) else {
+			import.unmapped.push(VscodeUnmapped {
+				key: display,
+				reason: "no known Zed action".to_string(),
+			});
+			continue;
+		};
+		let Some(zed_key) = vscode_key_to_zed(key) else {
+			import.unmapped.push(VscodeUnmapped {
+				key: display,
+				reason: "unsupported key".to_string(),
+			});
+			continue;
+		};
+
+		let label = match item.get("when").and_then(|v| v.as_str()) {
+			Some(when) => format!("{} (from {command}, when '{when}' not translated)", mapping.zed),
+			None => format!("{} (from {command})", mapping.zed),
+		};
+		import.bindings.push(KeymapActionBinding {
+			context: Some(mapping.context.to_string()),
+			key: zed_key,
+			action: Value::String(mapping.zed.to_string()),
+			label,
+		});
+	}
+
+	import
+}
+
+/// The Zed language name of a VS Code language id (e.g., `rust` => `Rust`, `typescriptreact` => `TSX`).
+pub fn vscode_language_name(id: &str) -> Option<&'static str> {
+	LANGUAGE_NAMES
+		.iter()
+		.find(|(vscode_id, _)| *vscode_id == id)
+		.map(|(_, name)| *name)
+}
+
+/// Convert a VS Code key (e.g., `ctrl+shift+p`, `ctrl+k ctrl+c`) to a Zed key (e.g., `ctrl-shift-p`, `ctrl-k ctrl-c`).
+pub fn vscode_key_to_zed(key: &str) -> Option<String> {
+	let mut strokes = Vec::new();
+	for stroke in key.split_whitespace() {
+		let stroke = stroke.to_lowercase();
+		// the `+` key itself (e.g., `ctrl++`)
+		let (modifiers, key) = if stroke == "+" {
+			("", "+")
+		} else if let Some(modifiers) = stroke.strip_suffix("++") {
+			(modifiers, "+")
+		} else {
+			stroke.rsplit_once('+').unwrap_or(("", stroke.as_str()))
+		};
+
+		let mut parts = Vec::new();
+		for modifier in modifiers.split('+').filter(|m| !m.is_empty()) {
+			let modifier = match modifier {
+				"ctrl" | "shift" | "alt" | "cmd" => modifier,
+				"meta" => "cmd",
+				"win" => "super",
+				_ => return None,
+			};
+			parts.push(modifier);
+		}
+
+		// VS Code scan code and OEM keys have no Zed equivalent
+		if key.is_empty() || key.starts_with('[') || key.starts_with("oem_") {
+			return None;
+		}
+		parts.push(key);
+		strokes.push(parts.join("-"));
+	}
+
+	if strokes.is_empty() {
+		None
+	} else {
+		Some(strokes.join(" "))
+	}
+}
+
+// region:    --- Support
+
+/// Ok(None) for the settings used by another mapping (and the vim extension settings without mapping).
+fn map_setting(
+	key: &str,
+	value: &Value,
+	settings: &Map<String, Value>,
+) -> core::result::Result<Option<(&'static SettingMapping, Value)>, String> {
+	if RELATED_SETTINGS.contains(&key) {
+		return Ok(None);
+	}
+	let Some(mapping) = SETTING_MAPPINGS.iter().find(|m| m.vscode == key) else {
+		if VIM_EXTENSION_PREFIXES.iter().any(|prefix| key.starts_with(prefix)) {
+			return Err("vim extension setting without Zed equivalent (vim_mode set)".to_string());
+		}
+		return Err("no Zed equivalent".to_string());
+	};
+
+	match (mapping.convert)(value, settings) {
+		Some(zed_value) => Ok(Some((mapping, zed_value))),
+		None => Err(format!("unsupported value {value}")),
+	}
+}
+
+fn import_language_block(key: &str, value: &Value, import: &mut VscodeSettingsImport) {
+	let Some(block) = value.as_object() else {
+		return;
+	};
+
+	// e.g., "[javascript][typescript]"
+	let ids: Vec<&str> = key.split(['[', ']']).filter(|s| !s.trim().is_empty()).collect();
+
+	for id in ids {
+		let Some(language) = vscode_language_name(id) else {
+			import.unmapped.push(VscodeUnmapped {
+				key: format!("[{id}]"),
+				reason: "unknown language".to_string(),
+			});
+			continue;
+		};
+
+		for (sub_key, sub_value) in block.iter() {
+			let full_key = format!("[{id}].{sub_key}");
+			match map_setting(sub_key, sub_value, block) {
+				Ok(Some((mapping, zed_value))) if mapping.language => {
+					let mut zed_path = vec!["languages".to_string(), language.to_string()];
+					zed_path.extend(mapping.zed.split('.').map(|s| s.to_string()));
+					import.changes.push(VscodeSettingChange {
+						vscode_key: full_key,
+						zed_path,
+						value: zed_value,
+					});
+				}
+				Ok(Some(_)) => import.unmapped.push(VscodeUnmapped {
+					key: full_key,
+					reason: "not a language setting in Zed".to_string(),
+				}),
+				Ok(None) => (),
+				Err(reason) => import.unmapped.push(VscodeUnmapped { key: full_key, reason }),
+			}
+		}
+	}
+}
+
+// -- Converters
+
+fn number(value: &Value, _: &Map<String, Value>) -> Option<Value> {
+	value.is_number().then(|| value.clone())
+}
+
+fn boolean(value: &Value, _: &Map<String, Value>) -> Option<Value> {
+	value.is_boolean().then(|| value.clone())
+}
+
+fn inverted_bool(value: &Value, _: &Map<String, Value>) -> Option<Value> {
+	value.as_bool().map(|b| Value::Bool(!b))
+}
+
+fn on_off(value: &Value, _: &Map<String, Value>) -> Option<Value> {
+	value.as_bool().map(|b| json!(if b { "on" } else { "off" }))
+}
+
+/// VS Code font family is a CSS list (e.g., `'Fira Code', Menlo, monospace`), Zed takes one family.
+fn first_font_family(value: &Value, _: &Map<String, Value>) -> Option<Value> {
+	let first = value.as_str()?.split(',').next()?.trim().trim_matches(['\'', '"']);
+	(!first.is_empty()).then(|| json!(first))
+}
+
+/// VS Code line height is in pixels (or a factor when < 8), Zed takes a factor.
+fn line_height(value: &Value, settings: &Map<String, Value>) -> Option<Value> {
+	let height = value.as_f64()?;
+	if height <= 0.0 {
+		return None;
+	}
+	let factor = if height < 8.0 {
+		height
+	} else {
+		let font_size = settings.get("editor.fontSize").and_then(|v| v.as_f64()).unwrap_or(14.0);
+		(height / font_size * 100.0).round() / 100.0
+	};
+	Some(json!({ "custom": factor }))
+}
+
+fn soft_wrap(value: &Value, _: &Map<String, Value>) -> Option<Value> {
+	let zed = match value.as_str()? {
+		"off" => "none",
+		"on" => "editor_width",
+		"wordWrapColumn" => "preferred_line_length",
+		"bounded" => "bounded",
+		_ => return None,
+	};
+	Some(json!(zed))
+}
+
+fn rulers(value: &Value, _: &Map<String, Value>) -> Option<Value> {
+	// rulers can be numbers or `{ "column": 80, "color": ... }`
+	let columns: Option<Vec<Value>> = value
+		.as_array()?
+		.iter()
+		.map(|r| r.as_u64().or_else(|| r.get("column")?.as_u64()).map(Value::from))
+		.collect();
+	columns.map(Value::Array)
+}
+
+fn show_whitespaces(value: &Value, _: &Map<String, Value>) -> Option<Value> {
+	match value.as_str()? {
+		v @ ("none" | "boundary" | "selection" | "all" | "trailing") => Some(json!(v)),
+		_ => None,
+	}
+}
+
+fn relative_line_numbers(value: &Value, _: &Map<String, Value>) -> Option<Value> {
+	match value.as_str()? {
+		"on" => Some(json!(false)),
+		"relative" => Some(json!(true)),
+		_ => None,
+	}
+}
+
+fn cursor_blink(value: &Value, _: &Map<String, Value>) -> Option<Value> {
+	let blinking = value.as_str()?;
+	Some(json!(blinking != "solid"))
+}
+
+fn cursor_shape(value: &Value, _: &Map<String, Value>) -> Option<Value> {
+	let zed = match value.as_str()? {
+		"line" | "line-thin" => "bar",
+		"block" => "block",
+		"block-outline" => "hollow",
+		"underline" | "underline-thin" => "underline",
+		_ => return None,
+	};
+	Some(json!(zed))
+}
+
+fn minimap_show(value: &Value, _: &Map<String, Value>) -> Option<Value> {
+	value.as_bool().map(|b| json!(if b { "always" } else { "never" }))
+}
+
+fn autosave(value: &Value, settings: &Map<String, Value>) -> Option<Value> {
+	let zed = match value.as_str()? {
+		"off" => json!("off"),
+		"afterDelay" => {
+			let delay = settings
+				.get("files.autoSaveDelay")
+				.and_then(|v| v.as_u64())
+				.unwrap_or(1000);
+			json!({ "after_delay": { "milliseconds": delay } })
+		}
+		"onFocusChange" => json!("on_focus_change"),
+		"onWindowChange" => json!("on_window_change"),
+		_ => return None,
+	};
+	Some(zed)
+}
+
+/// The Zed default exclusions, then the `files.exclude` globs set to true (Zed replaces the defaults with this list).
+fn files_exclude(value: &Value, _: &Map<String, Value>) -> Option<Value> {
+	let mut globs: Vec<&str> = ZED_DEFAULT_FILE_SCAN_EXCLUSIONS.to_vec();
+	for (glob, enabled) in value.as_object()?.iter() {
+		if enabled.as_bool() == Some(true) && !globs.contains(&glob.as_str()) {
+			globs.push(glob);
+		}
+	}
+	Some(json!(globs))
+}
+
+fn vim_system_clipboard(value: &Value, _: &Map<String, Value>) -> Option<Value> {
+	value.as_bool().map(|b| json!(if b { "always" } else { "never" }))
+}
+
+// endregion: --- Support
+
+// region:    --- Tests
+
+#[cfg(test)]
+mod tests {
+	type Result<T> = core::result::Result<T, Box<dyn std::error::Error>>;
+
+	use super::*;
+
+	#[test]
+	fn test_support_zed_vscode_import_settings_simple() -> Result<()> {
+		// -- Setup & Fixtures
+		let vscode = json!({
+			"editor.fontSize": 15,
+			"editor.fontFamily": "'Fira Code', Menlo, monospace",
+			"files.autoSave": "afterDelay",
+			"files.autoSaveDelay": 500,
+			"files.exclude": { "**/.git": true, "**/node_modules": true, "**/dist": false },
+			"workbench.colorTheme": "Monokai",
+			"vim.hlsearch": true,
+			"[rust]": { "editor.tabSize": 2, "editor.fontSize": 13 }
+		});
+
+		// -- Exec
+		let import = import_vscode_settings(&vscode);
+
+		// -- Check
+		let changes: Vec<(String, Value)> = import
+			.changes
+			.iter()
+			.map(|c| (c.zed_path.join("."), c.value.clone()))
+			.collect();
+		assert_eq!(
+			changes,
+			vec![
+				("buffer_font_size".to_string(), json!(15)),
+				("buffer_font_family".to_string(), json!("Fira Code")),
+				(
+					"autosave".to_string(),
+					json!({ "after_delay": { "milliseconds": 500 } })
+				),
+				(
+					"file_scan_exclusions".to_string(),
+					json!([
+						"**/.git",
+						"**/.svn",
+						"**/.hg",
+						"**/.jj",
+						"**/CVS",
+						"**/.DS_Store",
+						"**/Thumbs.db",
+						"**/.classpath",
+						"**/.settings",
+						"**/node_modules"
+					])
+				),
+				("languages.Rust.tab_size".to_string(), json!(2)),
+				("vim_mode".to_string(), json!(true)),
+			]
+		);
+		let unmapped: Vec<&str> = import.unmapped.iter().map(|u| u.key.as_str()).collect();
+		assert_eq!(
+			unmapped,
+			vec!["workbench.colorTheme", "vim.hlsearch", "[rust].editor.fontSize"]
+		);
+
+		Ok(())
+	}
+
+	#[test]
+	fn test_support_zed_vscode_import_keybindings_simple() -> Result<()> {
+		// -- Setup & Fixtures
+		let vscode = json!([
+			{ "key": "ctrl+shift+p", "command": "workbench.action.showCommands" },
+			{ "key": "cmd+k cmd+c", "command": "editor.action.commentLine", "when": "editorTextFocus" },
+			{ "key": "ctrl+alt+t", "command": "workbench.action.selectTheme" },
+			{ "key": "cmd+d", "command": "-editor.action.addSelectionToNextFindMatch" }
+		]);
+
+		// -- Exec
+		let import = import_vscode_keybindings(&vscode);
+
+		// -- Check
+		let bindings: Vec<(Option<&str>, &str, &Value)> = import
+			.bindings
+			.iter()
+			.map(|b| (b.context.as_deref(), b.key.as_str(), &b.action))
+			.collect();
+		assert_eq!(
+			bindings,
+			vec![
+				(Some("Workspace"), "ctrl-shift-p", &json!("command_palette::Toggle")),
+				(Some("Editor"), "cmd-k cmd-c", &json!("editor::ToggleComments")),
+			]
+		);
+		assert_eq!(import.unmapped.len(), 2);
+		assert_eq!(vscode_key_to_zed("ctrl++").as_deref(), Some("ctrl-+"));
+		assert_eq!(vscode_key_to_zed("ctrl+oem_102"), None);
+
+		Ok(())
+	}
+}
+
+// endregion: --- Tests